@@ -1,14 +1,141 @@
-use std::fs;
+use std::{
+    env,
+    fs,
+    path::{
+        Path,
+        PathBuf
+    },
+};
 
-use rustdoc_parser::process_html;
+use rustdoc_parser::{
+    helper_types::fragment::Fragment,
+    json::JsonCrate,
+    main_content::{
+        Anchored,
+        SectionContent
+    },
+    process_html_lenient,
+    source::SourcePage,
+    Page,
+};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let path = "/home/floupette/.rustup/toolchains/stable-x86_64-unknown-linux-gnu/share/doc/rust/html/std/result/enum.Result.html";
+    let mut verbose = false;
+    let mut path = String::from("/home/floupette/.rustup/toolchains/stable-x86_64-unknown-linux-gnu/share/doc/rust/html/std/result/enum.Result.html");
     // let path = "/home/floupette/.rustup/toolchains/stable-x86_64-unknown-linux-gnu/share/doc/rust/html/std/iter/trait.Iterator.html";
     // let path = "/home/floupette/.rustup/toolchains/stable-x86_64-unknown-linux-gnu/share/doc/rust/html/std/marker/trait.Sized.html";
     // let path = "//home/floupette/Projects/rocketman/target/doc/rocket/struct.Catcher.html";
-    let file = fs::read_to_string(path)?;
-    process_html(&file)?;
+    // only rustdoc JSON needs the item's path, such as `shapes::Shape`
+    let mut item = None;
+    // such as `Result::map`, looked for next to the page
+    let mut source = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-v" | "--verbose" => verbose = true,
+            "-s" | "--source" => source = Some(args.next().ok_or("--source needs an item, such as `Result::map`")?),
+            _ if path.ends_with(".json") && item.is_none() => item = Some(arg),
+            _ => path = arg,
+        }
+    }
+    if let Some(source) = source {
+        return print_source(Path::new(&path), &source);
+    }
+    let file = fs::read_to_string(&path)?;
+    if path.ends_with(".json") {
+        let krate = JsonCrate::parse(&file)?;
+        match item {
+            Some(item) => println!("{:#?}", krate.page(&item)?),
+            None => krate.items().iter().for_each(|item| println!("{}", item)),
+        }
+        return Ok(());
+    }
+    let parsed = process_html_lenient(&file)?;
+    println!("{:#?}", parsed.page);
+    print_layout(&parsed.page);
+    if verbose {
+        for diagnostic in &parsed.diagnostics {
+            eprintln!("Skipped : {}", diagnostic);
+        }
+    }
 
     Ok(())
 }
+
+/// Prints the type's size and alignment, as its Layout section tells them, one per line.
+fn print_layout(page: &Page) {
+    let layouts = page.main_content.0.iter().filter_map(|section| match &section.content {
+        SectionContent::Layout(layout) => Some(layout),
+        _ => None,
+    });
+    for layout in layouts {
+        println!("\nLayout");
+        let entries = layout.entries();
+        let width = entries.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+        for (name, value) in entries {
+            println!("  {:<width$}  {}", name, value, width = width);
+        }
+    }
+}
+
+/// Prints the source lines of an item, such as `Result` or `Result::map`, its page lying in the same directory as the given one.
+fn print_source(page: &Path, item: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let directory = page.parent().unwrap_or(Path::new("."));
+    // `Result::map` being either a type's member or a module's item
+    let (page, member) = match item.rsplit_once("::") {
+        Some((parent, member)) => match find_page(directory, parent.rsplit("::").next().unwrap_or(parent)) {
+            Some(page) => (page, Some(member)),
+            None => (find_page(directory, member).ok_or_else(|| format!("No page for `{}`", item))?, None),
+        },
+        None => (find_page(directory, item).ok_or_else(|| format!("No page for `{}`", item))?, None),
+    };
+    let file = fs::read_to_string(&page)?;
+    let parsed = process_html_lenient(&file)?;
+    let target = match member {
+        Some(member) => ["method", "tymethod"].iter().find_map(|kind| match parsed.page.find_anchor(&format!("{}.{}", kind, member)) {
+            Some(Anchored::Method(method)) => method.source.clone(),
+            _ => None,
+        }),
+        None => parsed.page.source.clone(),
+    };
+    let target = target.ok_or_else(|| format!("No source link for `{}`", item))?;
+
+    let file = target.split('#').next().unwrap_or_default();
+    let html = fs::read_to_string(page.parent().unwrap_or(Path::new(".")).join(file))?;
+    let source = SourcePage::parse(&html)?;
+    let (first, last) = match SourcePage::line_range(&target) {
+        Some((first, last)) => (first, source.item_end(first, last)),
+        None => (1, source.lines.len()),
+    };
+    for (number, line) in (first..).zip(source.lines(first, last)) {
+        println!("{:>5} {}", number, line.iter().map(paint).collect::<String>());
+    }
+
+    Ok(())
+}
+
+/// Looks for the page of the item going by that name, such as `enum.Result.html` for `Result`.
+fn find_page(directory: &Path, name: &str) -> Option<PathBuf> {
+    let suffix = format!(".{}.html", name);
+    fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_name()
+                .and_then(|file| file.to_str())
+                .is_some_and(|file| file.ends_with(&suffix) && file.matches('.').count() == 2)
+        })
+}
+
+/// Renders a Fragment in the terminal, colored ones in their very colors.
+fn paint(fragment: &Fragment) -> String {
+    match fragment {
+        Fragment::Colored(fragment, color) => {
+            let hex = color.to_hex().trim_start_matches('#');
+            let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2).unwrap_or_default(), 16).unwrap_or_default();
+            format!("\x1b[38;2;{};{};{}m{}\x1b[0m", channel(0), channel(2), channel(4), paint(fragment))
+        }
+        fragment => fragment.to_string(),
+    }
+}
@@ -0,0 +1,71 @@
+//! Shapes, and how to measure them.
+//!
+//! # Examples
+//!
+//! ```
+//! let square = shapes::Shape::Square(2.0);
+//! assert_eq!(square.area(), 4.0);
+//! ```
+
+/// A point on the plane.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    /// The horizontal coordinate.
+    pub x: f64,
+    /// The vertical coordinate.
+    pub y: f64,
+}
+
+/// A shape, which can be measured with [`Measure`].
+#[derive(Debug)]
+pub enum Shape {
+    /// A square, by the length of its **side**.
+    Square(f64),
+    /// A circle around its `center`.
+    Circle { center: Point, radius: f64 },
+    /// Nothing at all.
+    Empty,
+}
+
+impl Shape {
+    /// Returns the area of the shape.
+    ///
+    /// # Panics
+    ///
+    /// Never does.
+    pub fn area(&self) -> f64 {
+        match self {
+            Shape::Square(side) => side * side,
+            Shape::Circle { radius, .. } => 3.14 * radius * radius,
+            Shape::Empty => 0.0,
+        }
+    }
+
+    /// Scales the shape in place.
+    pub fn scale<T: Into<f64>>(&mut self, factor: T) -> &mut Self {
+        let _ = factor.into();
+        self
+    }
+}
+
+/// Anything with a size.
+pub trait Measure {
+    /// The unit it is measured in.
+    type Unit;
+
+    /// Measures the thing.
+    fn measure(&self) -> Self::Unit;
+
+    /// Whether the thing is empty.
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl Measure for Shape {
+    type Unit = f64;
+
+    fn measure(&self) -> f64 {
+        self.area()
+    }
+}
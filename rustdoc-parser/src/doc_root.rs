@@ -0,0 +1,232 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{
+        Component,
+        Path,
+        PathBuf
+    },
+    sync::OnceLock,
+};
+
+use rayon::prelude::*;
+
+use crate::{
+    errors::Herr,
+    process_html_with,
+    sidebar::Sidebar,
+    Page,
+    ParseOptions,
+    Parsed,
+};
+
+/////////////////////////////////////////////////////////////////////////////
+// DocRoot
+/////////////////////////////////////////////////////////////////////////////
+
+/// Every HTML page of a documentation tree, such as `share/doc/rust/html` or `target/doc`.
+///
+/// Files are read and parsed across all cores, one at a time on each : every Page is handed over
+/// as soon as it's parsed, its text being dropped once the caller is done with it.
+#[derive(Debug)]
+pub struct DocRoot {
+    root: PathBuf,
+    /// Relative to the root and sorted.
+    files: Vec<PathBuf>,
+    /// Each module's `sidebar-items.js`, by directory, read the first time one of its pages asks for it.
+    scripts: HashMap<PathBuf, (PathBuf, OnceLock<Option<String>>)>,
+}
+
+impl DocRoot {
+    /// Walks the directory, listing every HTML file below it, symbolic links to directories left aside.
+    ///
+    /// Only fails if the directory itself can't be read, files being read as they're parsed.
+    pub fn read(root: impl AsRef<Path>) -> Result<Self, Herr> {
+        let root = root.as_ref();
+        let mut paths = Vec::new();
+        let mut scripts = HashMap::new();
+        let mut directories = Vec::from([root.to_path_buf()]);
+        while let Some(directory) = directories.pop() {
+            let entries = match fs::read_dir(&directory) {
+                Ok(entries) => entries,
+                Err(err) if directory == root => return Err(io_error(root, err)),
+                // left out, as its pages can't be told apart
+                Err(_) => continue,
+            };
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let path = entry.path();
+                // not following links, which could lead back up the tree
+                if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                    directories.push(path);
+                } else if path.extension().is_some_and(|extension| extension == "html") {
+                    paths.push(path);
+                } else if is_sidebar_items(&path) {
+                    let directory = directory.strip_prefix(root).map(Path::to_path_buf).unwrap_or_default();
+                    scripts.insert(directory, (path, OnceLock::new()));
+                }
+            }
+        }
+        paths.sort_unstable();
+        let files = paths
+            .into_iter()
+            .map(|path| path.strip_prefix(root).map(Path::to_path_buf).unwrap_or(path))
+            .collect();
+
+        Ok(DocRoot {
+            root: root.to_path_buf(),
+            files,
+            scripts,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Paths of the HTML files, relative to the root and sorted.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(PathBuf::as_path)
+    }
+
+    /// Hands every file's Page over along with its path, in parallel and in no particular order.
+    ///
+    /// Redirections, source files and the like come out as `Herr::UnsupportedPageKind`s.
+    /// The module navigations are filled up out of their `sidebar-items.js`.
+    pub fn for_each(&self, visit: impl Fn(&Path, Result<Page<'_>, Herr>) + Sync) {
+        self.for_each_with(ParseOptions::default(), |path, parsed| visit(path, parsed.map(|parsed| parsed.page)))
+    }
+
+    /// Hands every file's Page over, failing on broken sections only if strict.
+    pub fn for_each_with(&self, options: ParseOptions, visit: impl Fn(&Path, Result<Parsed<'_>, Herr>) + Sync) {
+        self.files
+            .par_iter()
+            .for_each(|path| self.parse_file(path, options, |parsed| visit(path, parsed)))
+    }
+
+    /// Turns every file's Page into whatever the caller keeps of it, keeping to the order of the paths.
+    ///
+    /// What's kept can't borrow from the Page, whose text is dropped right after.
+    pub fn map<T: Send>(&self, keep: impl Fn(&Path, Result<Page<'_>, Herr>) -> T + Sync) -> Vec<T> {
+        self.files
+            .par_iter()
+            .map(|path| self.parse_file(path, ParseOptions::default(), |parsed| keep(path, parsed.map(|parsed| parsed.page))))
+            .collect()
+    }
+
+    /// Reads and parses a single file, the text living only as long as the caller's handling of the Page.
+    fn parse_file<T>(&self, path: &Path, options: ParseOptions, handle: impl FnOnce(Result<Parsed<'_>, Herr>) -> T) -> T {
+        let full_path = self.root.join(path);
+        let html = match fs::read_to_string(&full_path) {
+            Ok(html) => html,
+            Err(err) => return handle(Err(io_error(&full_path, err))),
+        };
+        let mut parsed = process_html_with(&html, options);
+        if let Ok(parsed) = &mut parsed {
+            self.fill_module(path, &mut parsed.page.sidebar);
+        }
+
+        handle(parsed)
+    }
+
+    /// Fills up the page's module navigation, which rustdoc leaves empty, out of the script lying next to the module's page.
+    fn fill_module(&self, page: &Path, sidebar: &mut Sidebar) {
+        let Some(module) = sidebar.module.as_mut().filter(|module| module.sections.is_empty()) else {
+            return;
+        };
+        let target = module.target.as_deref().unwrap_or("index.html");
+        let directory = page.parent().unwrap_or(Path::new("")).join(target);
+        let directory = normalize(directory.parent().unwrap_or(Path::new("")));
+        let script = self
+            .scripts
+            .get(&directory)
+            .and_then(|(path, script)| script.get_or_init(|| fs::read_to_string(path).ok()).as_deref());
+        if let Some(script) = script {
+            module.fill(script);
+        }
+    }
+}
+
+/// Whether the file is a module's items, such as `sidebar-items.js` or `sidebar-items1.95.0.js`.
+fn is_sidebar_items(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("sidebar-items") && name.ends_with(".js"))
+}
+
+/// Folds the `..`s of a relative path, such as `std/result/..` into `std`.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::Normal(name) => normalized.push(name),
+            _ => (),
+        }
+    }
+
+    normalized
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> Herr {
+    Herr::Io {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{
+            Path,
+            PathBuf
+        },
+    };
+
+    use super::DocRoot;
+    use crate::errors::Herr;
+
+    /// Removes the directory once dropped, even when an assertion fails.
+    struct TempDir(PathBuf);
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn parse_doc_root() {
+        let temp_dir = TempDir(std::env::temp_dir().join(format!("rustdoc-parser-doc-root-{}", std::process::id())));
+        let root = temp_dir.0.clone();
+        fs::create_dir_all(root.join("krate")).unwrap();
+        fs::write(
+            root.join("krate/struct.S.html"),
+            "<body class=\"rustdoc struct\"><nav class=\"sidebar\"><div class=\"sidebar-elems\"><h2><a href=\"index.html\">In krate</a></h2></div></nav>\
+                <section id=\"main-content\"></section></body>",
+        )
+        .unwrap();
+        fs::write(root.join("krate/S.html"), "<body><p>Redirecting...</p></body>").unwrap();
+        fs::write(root.join("krate/style.css"), "body {}").unwrap();
+        fs::write(root.join("krate/sidebar-items.js"), "window.SIDEBAR_ITEMS = {\"struct\":[\"S\"]};").unwrap();
+
+        let doc_root = DocRoot::read(&root).unwrap();
+        assert_eq!(doc_root.paths().collect::<Vec<&Path>>(), [Path::new("krate/S.html"), Path::new("krate/struct.S.html")]);
+        // each page's module navigation, for want of keeping the Pages themselves
+        let parsed = doc_root.map(|_, page| {
+            page.map(|page| page.sidebar.module.map(|module| module.sections[0].items[0].target.clone()))
+        });
+        assert!(matches!(parsed[0], Err(Herr::UnsupportedPageKind { .. })));
+        assert_eq!(parsed[1], Ok(Some(Some(String::from("struct.S.html")))));
+        let count = std::sync::atomic::AtomicUsize::new(0);
+        doc_root.for_each(|_, page| {
+            count.fetch_add(page.is_ok() as usize, std::sync::atomic::Ordering::Relaxed);
+        });
+        assert_eq!(count.into_inner(), 1);
+        drop(temp_dir);
+        assert!(matches!(DocRoot::read(root), Err(Herr::Io { .. })));
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     error::Error,
+    path::PathBuf,
     fmt::{
         Display,
         Formatter,
@@ -7,9 +8,110 @@ use std::{
         }
 };
 
-#[derive(Debug)]
+use crate::{
+    helper_types::html_element::HtmlElement,
+    json::FORMAT_VERSIONS,
+};
+
+/// Longest snippet of offending HTML an error carries, in characters.
+const SNIPPET_LENGTH: usize = 80;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "content", rename_all = "snake_case"))]
 pub enum Herr {
     Parsing(&'static str),
+    /// An element came in without the structure it was expected to have.
+    UnexpectedTag {
+        expected: &'static str,
+        found: String,
+        location: Location,
+        snippet: String,
+    },
+    /// A section the page can't go without is nowhere to be found.
+    MissingSection {
+        section: &'static str,
+        location: Location,
+        snippet: String,
+    },
+    /// The page is no item documentation, but a source file, a redirection, the settings...
+    UnsupportedPageKind {
+        kind: String,
+        location: Location,
+        snippet: String,
+    },
+    /// The rustdoc JSON is malformed, or lacks something it should hold.
+    Json(String),
+    /// The rustdoc JSON comes in a format version it can't be read in.
+    UnsupportedFormatVersion(u64),
+    /// No item goes by that path.
+    UnknownItem(String),
+    /// A file of the documentation couldn't be read.
+    Io {
+        path: PathBuf,
+        message: String,
+    },
+}
+
+/// Where an element starts within the HTML source.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Location {
+    /// Byte offset from the start of the source.
+    pub offset: usize,
+    /// Starting from 1.
+    pub line: usize,
+    /// In characters, starting from 1.
+    pub column: usize,
+}
+
+impl Herr {
+    /// The element doesn't hold what was expected of it.
+    pub fn unexpected_tag(expected: &'static str, element: &HtmlElement) -> Self {
+        Herr::UnexpectedTag {
+            expected,
+            found: element.kind.to_string(),
+            location: element.location(),
+            snippet: snippet(element),
+        }
+    }
+
+    /// The section should have been found within the element.
+    pub fn missing_section(section: &'static str, within: &HtmlElement) -> Self {
+        Herr::MissingSection {
+            section,
+            location: within.location(),
+            snippet: snippet(within),
+        }
+    }
+
+    /// The page is of a kind which can't be turned into a Page.
+    pub fn unsupported_page_kind(kind: &str, element: &HtmlElement) -> Self {
+        Herr::UnsupportedPageKind {
+            kind: String::from(kind),
+            location: element.location(),
+            snippet: snippet(element),
+        }
+    }
+
+    /// Where the error occured, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Herr::Parsing(_) | Herr::Json(_) | Herr::UnsupportedFormatVersion(_) | Herr::UnknownItem(_) | Herr::Io { .. } => None,
+            Herr::UnexpectedTag { location, .. }
+            | Herr::MissingSection { location, .. }
+            | Herr::UnsupportedPageKind { location, .. } => Some(*location),
+        }
+    }
+}
+
+/// Serializes the beginning of the element, so that it can be told apart.
+fn snippet(element: &HtmlElement) -> String {
+    let html = element.to_html();
+    match html.char_indices().nth(SNIPPET_LENGTH) {
+        Some((end, _)) => format!("{}…", &html[..end]),
+        None => html,
+    }
 }
 
 impl Error for Herr {}
@@ -17,6 +119,80 @@ impl Display for Herr {
     fn fmt(&self, f: &mut Formatter) -> Result {
         match self {
             Herr::Parsing(err) => write!(f, "{}", err),
+            Herr::UnexpectedTag {
+                expected,
+                found,
+                location,
+                snippet,
+            } => write!(f, "Expected {} in <{}> {} : {}", expected, found, location, snippet),
+            Herr::MissingSection {
+                section,
+                location,
+                snippet,
+            } => write!(f, "Missing {} within the element {} : {}", section, location, snippet),
+            Herr::UnsupportedPageKind {
+                kind,
+                location,
+                snippet,
+            } => write!(f, "Unsupported page kind `{}` {} : {}", kind, location, snippet),
+            Herr::Json(err) => write!(f, "Invalid rustdoc JSON : {}", err),
+            Herr::UnsupportedFormatVersion(version) => write!(
+                f,
+                "Unsupported rustdoc JSON format version {}, only {} to {} being read",
+                version,
+                FORMAT_VERSIONS.start(),
+                FORMAT_VERSIONS.end()
+            ),
+            Herr::UnknownItem(path) => write!(f, "No item named `{}`", path),
+            Herr::Io { path, message } => write!(f, "Unable to read {} : {}", path.display(), message),
         }
     }
 }
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "at line {}, column {} (byte {})", self.line, self.column, self.offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::Herr,
+        process_html,
+        process_html_lenient,
+    };
+
+    #[test]
+    fn located_errors() {
+        let redirection = "<!DOCTYPE html>\n<html>\n<body>\n<p>Redirecting to <a href=\"../x.html\">...</a></p>\n</body></html>";
+        let err = process_html(redirection).unwrap_err();
+        assert!(matches!(err, Herr::UnsupportedPageKind { .. }));
+        let location = err.location().unwrap();
+        assert_eq!((location.line, location.column, location.offset), (3, 1, 23));
+
+        let missing_header = "<body class=\"rustdoc enum\"><section id=\"main-content\">\n\
+            <h2 class=\"section-header\">Variants</h2><div class=\"variants\">\n  <section class=\"variant\"><h3>A</h3></section></div></section></body>";
+        match process_html(missing_header) {
+            Err(Herr::UnexpectedTag { expected, found, location, snippet }) => {
+                assert_eq!((expected, found.as_str()), ("a .code-header", "section"));
+                assert_eq!((location.line, location.column), (3, 3));
+                assert!(snippet.starts_with("<section class=\"variant\">"));
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn lenient_diagnostics() {
+        let page = "<body class=\"rustdoc enum\"><section id=\"main-content\">\
+            <h2 class=\"section-header\">Variants</h2><div class=\"variants\"><section class=\"variant\"><h3>A</h3></section></div>\
+            <h2 class=\"section-header\">Implementations</h2><div id=\"implementations-list\">\
+            <section class=\"impl\"><h3 class=\"code-header\">impl E</h3></section></div></section></body>";
+        assert!(process_html(page).is_err());
+        let parsed = process_html_lenient(page).unwrap();
+        assert_eq!(parsed.page.main_content.0.len(), 1);
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert!(matches!(parsed.diagnostics[0], Herr::UnexpectedTag { .. }));
+    }
+}
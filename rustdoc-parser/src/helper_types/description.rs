@@ -1,32 +1,58 @@
+use std::{
+    borrow::Cow,
+    fmt::{
+        self,
+        Display,
+        Formatter
+    },
+};
+
 use super::{
-    fragment::Fragment,
-    html_element::HtmlElement
+    fragment::{
+        self,
+        write_plain,
+        Fragment
+    },
+    html_element::{
+        HtmlElement,
+        Node
+    },
+    selector::selector,
 };
 
 /// Represents a method description, be it a couple of paragraphs, or entire subsections.
-#[derive(Debug)]
-pub struct Description {
-    pub introduction: Vec<Fragment>,
-    pub sections: Vec<DescriptionSection>,
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Description<'a> {
+    pub introduction: Vec<Fragment<'a>>,
+    pub sections: Vec<DescriptionSection<'a>>,
 }
 
-impl Description {
-    /// Parses an up to several lines long description.
-    pub fn parse(description: &str) -> Description {
-        let (_introduction, sections) = if let Some((__introduction, __sections)) = description.split_once("<h5") {
-            let _sections = __sections
-                .split("<h5")
-                .map(|section| DescriptionSection::parse(section))
-                .collect();
-            (__introduction, _sections)
-        } else {
-            (description, Vec::new())
-        };
-        // introduction does NOT necessarily contains <p>s
-        let introduction = _introduction
-            .split_inclusive("</p>\n")
-            .flat_map(|description_line| HtmlElement::extract("p", description_line).zip_content())
-            .collect();
+impl<'a> Description<'a> {
+    /// Parses an up to several lines long description out of its docblock.
+    pub fn parse(docblock: &HtmlElement<'a>) -> Description<'a> {
+        let mut introduction = Vec::new();
+        let mut sections: Vec<DescriptionSection> = Vec::new();
+        for child in docblock.children() {
+            let (fragments, is_block) = match child {
+                // introduction does NOT necessarily contains <p>s
+                Node::Content(raw_content) if raw_content.raw_content().is_some_and(|raw| raw.trim().is_empty()) => continue,
+                Node::Content(raw_content) => (Vec::from([raw_content.clone()]), false),
+                Node::Element(heading) if is_heading(heading) => {
+                    sections.push(DescriptionSection::parse(heading));
+                    continue;
+                }
+                Node::Element(block) => (DescriptionSection::parse_block(block), is_block(block)),
+            };
+            let content = match sections.last_mut() {
+                Some(section) => &mut section.content,
+                None => &mut introduction,
+            };
+            match is_block {
+                true => push_block(content, fragments),
+                false => content.extend(fragments),
+            }
+        }
 
         Description {
             introduction,
@@ -35,43 +61,132 @@ impl Description {
     }
 }
 
+impl Description<'_> {
+    /// Whether there's nothing to the description.
+    pub fn is_empty(&self) -> bool {
+        self.introduction.is_empty() && self.sections.is_empty()
+    }
+
+    /// Renders the description as Markdown, each of its sections under a heading of its own.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = fragment::to_markdown(&self.introduction);
+        for section in &self.sections {
+            markdown.push_str(&format!("\n\n#### {}\n\n{}", section.name, fragment::to_markdown(&section.content).trim_start()));
+        }
+
+        String::from(markdown.trim())
+    }
+}
+
+/// Plain text, each section starting with its name on a line of its own.
+impl Display for Description<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write_plain(f, &self.introduction)?;
+        for (index, section) in self.sections.iter().enumerate() {
+            if index > 0 || !self.introduction.is_empty() {
+                f.write_str("\n\n")?;
+            }
+            writeln!(f, "{}", section.name)?;
+            write_plain(f, &section.content)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Represents a description's possible subsection - Examples, Panics, and what have you.
 #[derive(Debug)]
-pub struct DescriptionSection {
-    pub name: Fragment,
-    pub content: Vec<Fragment>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DescriptionSection<'a> {
+    pub name: Fragment<'a>,
+    pub content: Vec<Fragment<'a>>,
 }
 
-impl DescriptionSection {
-    /// Parses a description possible subsection - Examples, Panics, and what have you.
-    fn parse(data: &str) -> Self {
-        let (_content, mut sections) = data.split_once("</h5>").unwrap();
-        let name = Fragment::Bold(Vec::from([HtmlElement::extract("a", _content).content[0].clone()]));
-        let mut content = Vec::new();
-        loop {
-            match sections.trim().chars().nth(1) {
-                Some('p') => {
-                    let (head, tail) = sections.split_once("</p>").unwrap();
-                    content.push(
-                        HtmlElement::parse(&mut format!("{}</p>", head).as_str()).zip_content(),
-                    );
-                    sections = tail;
-                }
-                Some('d') => {
-                    let (head, tail) = sections.split_once("</div>").unwrap();
-                    content.push(Vec::from([Fragment::CodeBlock(
-                        HtmlElement::extract("code", head).zip_code(),
-                    )]));
-                    sections = tail;
+impl<'a> DescriptionSection<'a> {
+    /// Starts a description possible subsection - Examples, Panics, and what have you - out of its heading.
+    fn parse(heading: &HtmlElement<'a>) -> Self {
+        DescriptionSection {
+            name: Fragment::Bold(heading.zip_content()),
+            content: Vec::new(),
+        }
+    }
+
+    /// Parses one of a description's blocks, be it a paragraph, a code block or a mere inline element.
+    fn parse_block(block: &HtmlElement<'a>) -> Vec<Fragment<'a>> {
+        match block.kind.as_ref() {
+            "div" | "pre" if block.has_class("example-wrap") || block.kind == "pre" => {
+                let code = block.select_first_with(selector!("code")).unwrap_or(block);
+                Vec::from([Fragment::CodeBlock(code.zip_code())])
+            }
+            "ul" | "ol" => {
+                let start = block.attribute("start").and_then(|start| start.parse().ok()).unwrap_or(1);
+                let mut items = Vec::new();
+                for (number, item) in (start..).zip(&block.inner_elements) {
+                    let marker = match block.kind == "ol" {
+                        true => Some(number),
+                        false => None,
+                    };
+                    push_item(&mut items, marker, item.zip_content());
                 }
-                Some(_) | None => break,
+
+                items
             }
+            "p" | "div" | "blockquote" | "details" | "dl" | "table" => block.zip_content(),
+            // inline elements straight in the docblock, as with short trait implementation docs
+            _ => Vec::from([block.zip_inner()]),
         }
+    }
+}
 
-        DescriptionSection {
-            name,
-            content: content.into_iter().flatten().collect(),
-        }
+/// Whether an element is one of the headings splitting a description into sections.
+fn is_heading(element: &HtmlElement) -> bool {
+    matches!(element.kind.as_ref(), "h1" | "h2" | "h3" | "h4" | "h5" | "h6")
+}
+
+/// Whether an element stands on its own, rather than running along the text around it.
+fn is_block(element: &HtmlElement) -> bool {
+    matches!(element.kind.as_ref(), "p" | "div" | "pre" | "ul" | "ol" | "blockquote" | "details" | "dl" | "table")
+}
+
+/// Adds a block to the content, a blank line apart from the one before, code blocks keeping their own line breaks.
+pub(crate) fn push_block<'a>(content: &mut Vec<Fragment<'a>>, block: Vec<Fragment<'a>>) {
+    let is_code = |fragment: Option<&Fragment>| matches!(fragment, Some(Fragment::CodeBlock(_)));
+    if !content.is_empty() && !is_code(content.last()) && !is_code(block.first()) {
+        content.push(Fragment::Raw(Cow::Borrowed("\n\n")));
+    }
+    content.extend(block);
+}
+
+/// Adds one of a list's items on a line of its own, after its number if the list is ordered or a dash otherwise.
+pub(crate) fn push_item<'a>(items: &mut Vec<Fragment<'a>>, number: Option<u64>, item: Vec<Fragment<'a>>) {
+    if !items.is_empty() {
+        items.push(Fragment::Raw(Cow::Borrowed("\n")));
     }
+    items.push(Fragment::Raw(match number {
+        Some(number) => Cow::Owned(format!("{}. ", number)),
+        None => Cow::Borrowed("- "),
+    }));
+    items.extend(item);
 }
 
+#[cfg(test)]
+mod tests {
+    use super::Description;
+    use crate::helper_types::html_element::HtmlElement;
+
+    #[test]
+    fn blocks() {
+        let html = "<div class=\"docblock\"><p>Sorts, see <code>Vec</code>.</p><p>Second paragraph.</p><ul><li>one</li><li>two</li></ul>\
+            <h2>Examples</h2><ol start=\"3\"><li>three</li><li>four</li></ol>\
+            <div class=\"example-wrap\"><pre class=\"rust\"><code>v.sort();</code></pre></div><p>Done.</p></div>";
+        let description = Description::parse(&HtmlElement::parse_document(html).inner_elements[0]);
+        assert_eq!(
+            description.to_string(),
+            "Sorts, see Vec.\n\nSecond paragraph.\n\n- one\n- two\n\nExamples\n3. three\n4. four\nv.sort();\nDone."
+        );
+        assert_eq!(
+            description.to_markdown(),
+            "Sorts, see `Vec`.\n\nSecond paragraph.\n\n- one\n- two\n\n#### Examples\n\n3. three\n4. four\n\n```\nv.sort();\n```\n\nDone."
+        );
+    }
+}
@@ -1,52 +1,187 @@
+use std::{
+    borrow::Cow,
+    fmt::{
+        self,
+        Display,
+        Formatter
+    },
+};
+
+use super::entity;
+
 /// Represents a single piece of information, be it a full sentence or a mere symbol.
 ///
 /// Shall help bring the important parts out when rendering the documentation in the terminal.
+/// Raw text borrows from the parsed document wherever it can.
 #[derive(Clone, Debug, PartialEq)]
-pub enum Fragment {
-    Bold(Vec<Fragment>),
-    Code(Box<Fragment>),
-    CodeBlock(Box<Fragment>),
-    Colored(Box<Fragment>, Color),
-    Raw(String)
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "content", rename_all = "snake_case"))]
+pub enum Fragment<'a> {
+    Bold(Vec<Fragment<'a>>),
+    Code(Box<Fragment<'a>>),
+    CodeBlock(Box<Fragment<'a>>),
+    Colored(Box<Fragment<'a>>, Color),
+    /// Such as `[Vec](struct.Vec.html)`, the href being left as the page has it.
+    Link { content: Box<Fragment<'a>>, href: Cow<'a, str> },
+    Raw(Cow<'a, str>)
 }
 
-impl Fragment {
+impl<'a> Fragment<'a> {
     /// Helps retrieve the inner String out of a Fragment::Raw.
-    pub fn raw_content(&self) -> &str {
-        let Fragment::Raw(raw_content) = self else {
-            panic!("Tried to call raw_content on an invalid Fragment")
-        };
-
-        raw_content
+    pub fn raw_content(&self) -> Option<&str> {
+        match self {
+            Fragment::Raw(raw_content) => Some(raw_content),
+            _ => None,
+        }
     }
 
     /// Uses the un_escape function to un_escape the full inner content of a Fragment.
-    pub fn un_escape_content(&self) -> Self {
+    ///
+    /// Only meant for Fragments built out of still escaped text, HtmlElement's are decoded already.
+    pub fn un_escape_content(self) -> Self {
         match self {
             Fragment::Bold(fragments) => Fragment::Bold(
                 fragments
                     .into_iter()
-                    .map(|f| f.un_escape_content())
+                    .map(Fragment::un_escape_content)
                     .collect::<Vec<Fragment>>(),
             ),
             Fragment::Code(boxed_fragment) => Fragment::Code(Box::new(boxed_fragment.un_escape_content())),
             Fragment::CodeBlock(boxed_fragment) => Fragment::CodeBlock(Box::new(boxed_fragment.un_escape_content())),
-            Fragment::Colored(boxed_fragment, color) => Fragment::Colored(Box::new(boxed_fragment.un_escape_content()), color.clone()),
-            Fragment::Raw(ref raw_content) => Fragment::Raw(un_escape(raw_content)),
+            Fragment::Colored(boxed_fragment, color) => Fragment::Colored(Box::new(boxed_fragment.un_escape_content()), color),
+            Fragment::Link { content, href } => Fragment::Link {
+                content: Box::new(content.un_escape_content()),
+                href: Cow::Owned(un_escape(&href)),
+            },
+            Fragment::Raw(Cow::Borrowed(raw_content)) => Fragment::Raw(entity::decode(raw_content)),
+            Fragment::Raw(Cow::Owned(raw_content)) => match entity::decode(&raw_content) {
+                Cow::Borrowed(_) => Fragment::Raw(Cow::Owned(raw_content)),
+                Cow::Owned(decoded) => Fragment::Raw(Cow::Owned(decoded)),
+            },
+        }
+    }
+
+    /// Copies whatever the Fragment borrows, so that it may outlive its document.
+    pub fn into_owned(self) -> Fragment<'static> {
+        match self {
+            Fragment::Bold(fragments) => Fragment::Bold(fragments.into_iter().map(Fragment::into_owned).collect()),
+            Fragment::Code(boxed_fragment) => Fragment::Code(Box::new(boxed_fragment.into_owned())),
+            Fragment::CodeBlock(boxed_fragment) => Fragment::CodeBlock(Box::new(boxed_fragment.into_owned())),
+            Fragment::Colored(boxed_fragment, color) => Fragment::Colored(Box::new(boxed_fragment.into_owned()), color),
+            Fragment::Link { content, href } => Fragment::Link {
+                content: Box::new(content.into_owned()),
+                href: Cow::Owned(href.into_owned()),
+            },
+            Fragment::Raw(raw_content) => Fragment::Raw(Cow::Owned(raw_content.into_owned())),
         }
     }
 }
 
+impl Fragment<'_> {
+    /// Renders the Fragment as Markdown, code being put in backticks and bold text in asterisks.
+    ///
+    /// Links are rendered as `[text](href)`, their href untouched.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        self.push_markdown(&mut markdown);
+
+        markdown
+    }
+
+    fn push_markdown(&self, markdown: &mut String) {
+        match self {
+            Fragment::Bold(fragments) => {
+                let bold = to_markdown(fragments);
+                if !bold.trim().is_empty() {
+                    markdown.push_str(&format!("**{}**", bold));
+                }
+            }
+            Fragment::Code(fragment) => markdown.push_str(&inline_code(&fragment.to_string())),
+            Fragment::CodeBlock(fragment) => markdown.push_str(&code_block(&fragment.to_string(), "")),
+            Fragment::Colored(fragment, _) => fragment.push_markdown(markdown),
+            Fragment::Link { content, href } => {
+                markdown.push('[');
+                content.push_markdown(markdown);
+                // destinations with spaces or parentheses go in angle brackets
+                match href.contains(|character: char| character.is_whitespace() || matches!(character, '(' | ')')) {
+                    true => markdown.push_str(&format!("](<{}>)", href)),
+                    false => markdown.push_str(&format!("]({})", href)),
+                }
+            }
+            Fragment::Raw(raw_content) => {
+                for character in raw_content.chars() {
+                    if matches!(character, '\\' | '*' | '_' | '`' | '[' | ']' | '<') {
+                        markdown.push('\\');
+                    }
+                    markdown.push(character);
+                }
+            }
+        }
+    }
+}
+
+/// Plain text, with neither formatting nor colors.
+impl Display for Fragment<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Fragment::Bold(fragments) => write_plain(f, fragments),
+            Fragment::Code(fragment) | Fragment::Colored(fragment, _) | Fragment::Link { content: fragment, .. } => write!(f, "{}", fragment),
+            Fragment::CodeBlock(fragment) => write!(f, "\n{}\n", fragment),
+            Fragment::Raw(raw_content) => f.write_str(raw_content),
+        }
+    }
+}
+
+/// Writes the Fragments one after the other as plain text.
+pub fn write_plain(f: &mut Formatter, fragments: &[Fragment]) -> fmt::Result {
+    fragments.iter().try_for_each(|fragment| write!(f, "{}", fragment))
+}
+
+/// Renders the Fragments one after the other as Markdown.
+pub fn to_markdown(fragments: &[Fragment]) -> String {
+    let mut markdown = String::new();
+    for fragment in fragments {
+        fragment.push_markdown(&mut markdown);
+    }
+
+    markdown
+}
+
+/// Puts the text in as many backticks as it takes for none of its own to end the code span.
+pub fn inline_code(text: &str) -> String {
+    let fence = "`".repeat(longest_run(text, '`') + 1);
+    match text.starts_with('`') || text.ends_with('`') {
+        true => format!("{} {} {}", fence, text, fence),
+        false => format!("{}{}{}", fence, text, fence),
+    }
+}
+
+/// Fences the code in a block, tagged with the language if any.
+pub fn code_block(code: &str, language: &str) -> String {
+    let fence = "`".repeat(longest_run(code, '`').max(2) + 1);
+    format!("\n\n{}{}\n{}\n{}\n\n", fence, language, code.trim_matches('\n'), fence)
+}
+
+/// Length of the longest run of the character within the text.
+fn longest_run(text: &str, character: char) -> usize {
+    text.split(|other| other != character)
+        .map(str::len)
+        .max()
+        .unwrap_or(0)
+}
+
 /// Turns escaped character back into plain characters.
+///
+/// Text is already un_escaped once extracted from the HTML : un_escaping it again would turn
+/// a literal `&amp;lt;` into `<` instead of `&lt;`.
 pub fn un_escape(raw: &str) -> String {
-    raw.replace("&lt;", "<")
-        .replace("&gt;", ">")
-        .replace("&amp;", "&")
-        .replace("&#x27;", "'")
+    entity::decode(raw).into_owned()
 }
 
 /// Will help in the process of highlighting content when rendering said content in the terminal.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Color {
     AssociatedType,
     Enum,
@@ -55,6 +190,18 @@ pub enum Color {
     Primitive,
     Struct,
     Trait,
+    // the highlighting of source code
+    Attribute,
+    Comment,
+    DocComment,
+    Keyword,
+    Lifetime,
+    /// Strings, numbers and booleans.
+    Literal,
+    /// Such as `Option` or `Some`.
+    Prelude,
+    QuestionMark,
+    SelfValue,
 }
 
 impl Color {
@@ -67,6 +214,50 @@ impl Color {
             Color::Primitive => "#2dbfb8",
             Color::Struct => "#2dbfb8",
             Color::Trait => "#b78cf2",
+            Color::Attribute => "#ee6868",
+            Color::Comment => "#8d8d8b",
+            Color::DocComment => "#8ca375",
+            Color::Keyword => "#ab8ac1",
+            Color::Lifetime => "#d97f26",
+            Color::Literal => "#83a300",
+            Color::Prelude => "#769acb",
+            Color::QuestionMark => "#ff9011",
+            Color::SelfValue => "#ee6868",
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{
+        to_markdown,
+        Color,
+        Fragment
+    };
+
+    #[test]
+    fn plain_text_and_markdown() {
+        let fragments = [
+            Fragment::Raw(Cow::from("Returns the ")),
+            Fragment::Code(Box::new(Fragment::Raw(Cow::from("Vec<T>")))),
+            Fragment::Raw(Cow::from(", ")),
+            Fragment::Bold(Vec::from([Fragment::Raw(Cow::from("sorted"))])),
+            Fragment::Raw(Cow::from(" in *place*, through ")),
+            Fragment::Colored(Box::new(Fragment::Raw(Cow::from("Ord"))), Color::Trait),
+            Fragment::Code(Box::new(Fragment::Raw(Cow::from("`x`")))),
+            Fragment::CodeBlock(Box::new(Fragment::Raw(Cow::from("v.sort();")))),
+            Fragment::Link {
+                content: Box::new(Fragment::Code(Box::new(Fragment::Raw(Cow::from("sort"))))),
+                href: Cow::from("struct.Vec.html#method.sort"),
+            },
+        ];
+        let plain = fragments.iter().map(Fragment::to_string).collect::<String>();
+        assert_eq!(plain, "Returns the Vec<T>, sorted in *place*, through Ord`x`\nv.sort();\nsort");
+        assert_eq!(
+            to_markdown(&fragments),
+            "Returns the `Vec<T>`, **sorted** in \\*place\\*, through Ord`` `x` ``\n\n```\nv.sort();\n```\n\n[`sort`](struct.Vec.html#method.sort)"
+        );
+    }
+}
@@ -1,13 +1,29 @@
-use std::collections::BTreeMap;
+use std::{
+    borrow::Cow,
+    ops::Range,
+};
+
+use crate::errors::{
+    Herr,
+    Location,
+};
 
 use super::{
     fragment::{
         Color,
         Fragment
         },
+    selector::Selector,
     tag::{
+        Attributes,
         Tag,
         TagKind
+        },
+    tokenizer::{
+        is_raw_text,
+        is_void,
+        Token,
+        Tokenizer
         }
 };
 
@@ -18,216 +34,637 @@ use super::{
 /// - optional additional attributes, such as "id", "class", ...
 /// - optional raw content
 /// - optional inner elements
-#[derive(Clone, Debug, PartialEq)]
-pub struct HtmlElement {
-    pub kind: String,
-    attributes: BTreeMap<String, String>,
-    pub content: Vec<Fragment>,
-    pub inner_elements: Vec<HtmlElement>,
+///
+/// Borrows its names, attributes and text from the parsed HTML, only owning text which had character references decoded.
+///
+/// Two elements are equal whenever their content is, wherever they come from.
+#[derive(Clone, Debug)]
+pub struct HtmlElement<'a> {
+    pub kind: Cow<'a, str>,
+    attributes: Attributes<'a>,
+    pub content: Vec<Fragment<'a>>,
+    pub inner_elements: Vec<HtmlElement<'a>>,
+    children: Vec<Child>,
+    location: Location,
 }
 
-impl HtmlElement {
-    /// Extract the content of a sepcific element and returns its full content as Fragments.
-    pub fn extract(name: &str, data: &str) -> HtmlElement {
-        let closed_tag = format!("<{}>", name);
-        let opened_tag = format!("<{}", name);
-        let untag = closed_tag.replace('<', "</");
-        let tail = if let Some((_, tail)) = data.split_once(&closed_tag) {
-            format!("{}{}", closed_tag, tail)
-        } else if let Some((_, tail)) = data.split_once(&opened_tag) {
-            format!("{}{}", opened_tag, tail)
-        } else {
-            String::from(data)
-        };
-        let mut tag = tail
-            .split_inclusive(&untag)
-            .next()
-            .expect("extract_tag failed to get rid of the trailing data");
+impl PartialEq for HtmlElement<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.attributes == other.attributes
+            && self.content == other.content
+            && self.inner_elements == other.inner_elements
+            && self.children == other.children
+    }
+}
 
-        HtmlElement::parse(&mut tag)
+/// Keeps track of the order content and inner elements come in, as they are stored apart.
+///
+/// Only recorded once an element holds both, the order being plain otherwise.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Child {
+    Content(usize),
+    Element(usize),
+}
+
+/// Any of an HtmlElement's children, be it raw content or an inner element.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Node<'r, 'a> {
+    Content(&'r Fragment<'a>),
+    Element(&'r HtmlElement<'a>),
+}
+
+impl<'a> HtmlElement<'a> {
+    /// Builds a new, empty HtmlElement out of its opening tag.
+    pub fn new(tag: Tag<'a>) -> Self {
+        HtmlElement {
+            kind: tag.name,
+            attributes: tag.attributes,
+            content: Vec::new(),
+            inner_elements: Vec::new(),
+            children: Vec::new(),
+            location: Location::default(),
         }
+    }
+
+    /// Builds a new, empty HtmlElement found at the given location.
+    pub(crate) fn located(tag: Tag<'a>, location: Location) -> Self {
+        HtmlElement {
+            location,
+            ..HtmlElement::new(tag)
+        }
+    }
+
+    /// Where the element's opening tag starts within the parsed HTML.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Extract the content of a sepcific element and returns its full content as Fragments.
+    ///
+    /// Falls back onto the whole of the data when no such element is to be found.
+    pub fn extract(name: &str, data: &'a str) -> HtmlElement<'a> {
+        HtmlElement::parse_document(data).into_first(name)
+    }
+
+    /// Builds a tree out of a whole document, or any piece of one, under a nameless root element.
+    ///
+    /// Just as browsers do, misnested and unclosed elements are closed where they're implied to be.
+    pub fn parse_document(html: &'a str) -> HtmlElement<'a> {
+        let mut tokenizer = Tokenizer::new(html);
+        let location = tokenizer.location();
+        let root = HtmlElement::located(
+            Tag {
+                name: Cow::Borrowed(""),
+                kind: TagKind::Opening,
+                attributes: Vec::new(),
+            },
+            location,
+        );
+        let (root, _) = HtmlElement::build(&mut tokenizer, root, true);
+
+        root
+    }
+
     /// Build a new HtmlElement out of an html element and all of its inner elements.
     ///
     /// Makes use of the :
+    /// - Tokenizer type to cut the html into tags and text,
     /// - Tag type to assess the full identity of an opening, closing or void tag,
     /// - Fragment type to segment its content into formatted, or not, entities.
     ///
-    /// # Panics
+    /// Anything preceding the first element is skipped, and the html is advanced right past its end.
+    pub fn parse(html: &mut &'a str) -> Self {
+        let mut tokenizer = Tokenizer::new(html);
+        let mut skipped = HtmlElement::parse_document("");
+        loop {
+            let location = tokenizer.location();
+            let Some(token) = tokenizer.next() else {
+                break;
+            };
+            match token {
+                Token::StartTag { tag, self_closing } => {
+                    let element = HtmlElement::located(tag, location);
+                    let element = HtmlElement::grow(&mut tokenizer, element, self_closing);
+                    *html = html[tokenizer.offset()..].trim();
+                    return element;
+                }
+                Token::Text(text) => skipped.push_content(text),
+                Token::Comment(_) | Token::Doctype(_) | Token::EndTag(_) => (),
+            }
+        }
+        *html = "";
+
+        skipped
+    }
+
+    /// Builds the element whose start tag was just read out of the tokens coming next, up to its end.
     ///
-    /// Panics if the HtmlElement cannot be built all the way through
-    pub fn parse(html: &mut &str) -> Self {
-        let main_tag_end = html
-            .find('>')
-            .expect("Html::build() failed : can't compute main_tag_end");
-        let main_tag = Tag::parse(&html[1..main_tag_end]);
-        let attributes = main_tag.attributes;
-        let mut content: Vec<Fragment> = Vec::new();
-        let mut inner_elements: Vec<HtmlElement> = Vec::new();
-
-        *html = html[main_tag_end + 1..].trim();
+    /// The tokenizer is left right past the element, even when it's closed by the start of another one.
+    pub(crate) fn grow(tokenizer: &mut Tokenizer<'a>, element: HtmlElement<'a>, self_closing: bool) -> Self {
+        HtmlElement::finish(tokenizer, element, self_closing, true)
+    }
+
+    /// Moves the tokenizer past the element whose start tag was just read, without building it up.
+    pub(crate) fn skip(tokenizer: &mut Tokenizer<'a>, element: HtmlElement<'a>, self_closing: bool) {
+        HtmlElement::finish(tokenizer, element, self_closing, false);
+    }
+
+    fn finish(tokenizer: &mut Tokenizer<'a>, element: HtmlElement<'a>, self_closing: bool, keep: bool) -> Self {
+        if is_void(&element.kind) || (self_closing && is_foreign(&element.kind)) {
+            return element;
+        }
+        let (element, end) = HtmlElement::build(tokenizer, element, keep);
+        if end < tokenizer.offset() {
+            tokenizer.rewind(end);
+        }
+
+        element
+    }
 
+    /// Builds every element starting within the span of the document, located within the whole of it.
+    pub fn parse_span(html: &'a str, span: Range<usize>) -> Vec<HtmlElement<'a>> {
+        let mut tokenizer = Tokenizer::starting_at(html, span.start);
+        let mut elements = Vec::new();
+        while tokenizer.offset() < span.end {
+            let location = tokenizer.location();
+            let Some(token) = tokenizer.next() else {
+                break;
+            };
+            if let Token::StartTag { tag, self_closing } = token {
+                let element = HtmlElement::located(tag, location);
+                elements.push(HtmlElement::grow(&mut tokenizer, element, self_closing));
+            }
+        }
+
+        elements
+    }
+
+    /// Grows the bottom element out of the tokens, keeping track of the elements opened above it.
+    ///
+    /// Unless kept, the content and inner elements are dropped as they come, only the structure being followed.
+    ///
+    /// Returns the bottom element once closed, along with the offset at which the remaining html starts.
+    fn build(tokenizer: &mut Tokenizer<'a>, mut bottom: HtmlElement<'a>, keep: bool) -> (HtmlElement<'a>, usize) {
+        let mut open: Vec<HtmlElement> = Vec::new();
         loop {
-            if html.starts_with('<') {
-                let current_tag_end = html
-                    .find('>')
-                    .expect("Html::build() failed : can't compute current_current_tag_end");
-                let current_tag = Tag::parse(&html[1..current_tag_end]);
-                match current_tag.kind {
-                    TagKind::Void => {
-                        inner_elements.push(HtmlElement {
-                            kind: current_tag.name,
-                            attributes: current_tag.attributes,
-                            content: Vec::new(),
-                            inner_elements: Vec::new(),
-                        });
-
-                        *html = html[current_tag_end + 1..].trim();
+            let location = tokenizer.location();
+            let Some(token) = tokenizer.next() else {
+                break;
+            };
+            match token {
+                Token::Text(text) if keep => open.last_mut().unwrap_or(&mut bottom).push_content(text),
+                Token::Text(_) => (),
+                Token::StartTag { tag, self_closing } => {
+                    while let Some(depth) = implied_end(&bottom, &open, &tag.name) {
+                        if HtmlElement::close(&mut bottom, &mut open, depth, keep) {
+                            return (bottom, location.offset);
+                        }
                     }
-                    TagKind::Opening => inner_elements.push(HtmlElement::parse(html)),
-                    TagKind::Closing => {
-                        *html = html[current_tag_end + 1..].trim();
-
-                        return HtmlElement {
-                            kind: main_tag.name,
-                            attributes,
-                            content,
-                            inner_elements,
-                        };
+                    let foreign = self_closing
+                        && (is_foreign(&tag.name)
+                            || is_foreign(&bottom.kind)
+                            || open.iter().any(|element| is_foreign(&element.kind)));
+                    let void = tag.kind == TagKind::Void || foreign;
+                    let element = HtmlElement::located(tag, location);
+                    if void {
+                        if keep {
+                            open.last_mut().unwrap_or(&mut bottom).push_element(element);
+                        }
+                    } else {
+                        open.push(element);
                     }
                 }
-            } else {
-                if html.is_empty() {
-                    return HtmlElement {
-                        kind: main_tag.name,
-                        attributes,
-                        content,
-                        inner_elements,
-                    };
+                Token::EndTag(tag) => {
+                    if let Some(index) = open.iter().rposition(|element| element.kind == tag.name) {
+                        HtmlElement::close(&mut bottom, &mut open, index + 1, keep);
+                    } else if bottom.kind == tag.name {
+                        HtmlElement::close(&mut bottom, &mut open, 0, keep);
+                        return (bottom, tokenizer.offset());
+                    } else if tag.name == "br" && keep {
+                        // browsers read a stray `</br>` as a `<br>`
+                        let br = HtmlElement::located(
+                            Tag {
+                                kind: TagKind::Void,
+                                ..tag
+                            },
+                            location,
+                        );
+                        open.last_mut().unwrap_or(&mut bottom).push_element(br);
+                    }
                 }
-                let next_element_start = html
-                    .find('<')
-                    .expect("Html::build() failed : can't compute the next element");
-                content.push(Fragment::Raw(String::from(&html[..next_element_start])));
+                Token::Comment(_) | Token::Doctype(_) => (),
+            }
+        }
+        HtmlElement::close(&mut bottom, &mut open, 0, keep);
+
+        (bottom, tokenizer.offset())
+    }
+
+    /// Closes every open element down to the depth, each one into its parent unless dropped, the bottom element being at depth 0.
+    ///
+    /// Returns whether the bottom element was closed as well.
+    fn close(bottom: &mut HtmlElement<'a>, open: &mut Vec<HtmlElement<'a>>, depth: usize, keep: bool) -> bool {
+        if !keep {
+            open.truncate(depth.saturating_sub(1));
+            return depth == 0;
+        }
+        while open.len() >= depth.max(1) {
+            let Some(closed) = open.pop() else {
+                break;
+            };
+            open.last_mut().unwrap_or(&mut *bottom).push_element(closed);
+        }
 
-                *html = &html[next_element_start..];
+        depth == 0
+    }
+
+    /// Appends some raw content, merging it with the previous piece of raw content if need be.
+    pub(crate) fn push_content(&mut self, text: Cow<'a, str>) {
+        if let Some(Child::Content(index)) = self.last_child()
+            && let Fragment::Raw(previous) = &mut self.content[index]
+        {
+            previous.to_mut().push_str(&text);
+            return;
+        }
+        self.record(Child::Content(self.content.len()));
+        self.content.push(Fragment::Raw(text));
+    }
+
+    /// Appends an inner element.
+    pub(crate) fn push_element(&mut self, element: HtmlElement<'a>) {
+        self.record(Child::Element(self.inner_elements.len()));
+        self.inner_elements.push(element);
+    }
+
+    /// Records where a new child comes in, once the element holds both content and inner elements.
+    fn record(&mut self, child: Child) {
+        if self.children.is_empty() {
+            match child {
+                Child::Content(_) if !self.inner_elements.is_empty() => {
+                    self.children.extend((0..self.inner_elements.len()).map(Child::Element))
+                }
+                Child::Element(_) if !self.content.is_empty() => {
+                    self.children.extend((0..self.content.len()).map(Child::Content))
+                }
+                _ => return,
             }
         }
+        self.children.push(child);
     }
 
-    /// Returns the inner block's full content as fragments through zipping recursively.
-    pub fn zip_content(&mut self) -> Vec<Fragment> {
-        if !self.inner_elements.is_empty() && self.inner_elements[0].kind == "strong" {
-            return Vec::from([
-                Fragment::Bold(Vec::from([self.inner_elements[0].content[0].clone()])),
-                self.content[0].clone(),
-            ]);
-        }
-        let content = &self.content;
-        let inner_elements_content = self
+    fn last_child(&self) -> Option<Child> {
+        match self.children.last() {
+            Some(child) => Some(*child),
+            None if self.inner_elements.is_empty() => self.content.len().checked_sub(1).map(Child::Content),
+            None => Some(Child::Element(self.inner_elements.len() - 1)),
+        }
+    }
+
+    /// Iterates over the raw content and inner elements alike, in the order they come in.
+    pub fn children(&self) -> impl Iterator<Item = Node<'_, 'a>> {
+        let (content, inner_elements) = match self.children.is_empty() {
+            true => (self.content.as_slice(), self.inner_elements.as_slice()),
+            false => (&[][..], &[][..]),
+        };
+        self.children
+            .iter()
+            .map(|child| match child {
+                Child::Content(index) => Node::Content(&self.content[*index]),
+                Child::Element(index) => Node::Element(&self.inner_elements[*index]),
+            })
+            .chain(content.iter().map(Node::Content))
+            .chain(inner_elements.iter().map(Node::Element))
+    }
+
+    /// Returns the value of one of the element's attributes, if present.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Whether the class attribute lists the given class.
+    pub fn has_class(&self, class: &str) -> bool {
+        self.attribute("class")
+            .is_some_and(|classes| classes.split_ascii_whitespace().any(|name| name == class))
+    }
+
+    /// Returns every element below this one matching a CSS selector, in document order.
+    ///
+    /// The selector being parsed on each call, select_with spares that to selectors used over and over.
+    pub fn select(&self, selector: &str) -> Result<Vec<&HtmlElement<'a>>, Herr> {
+        Ok(Selector::parse(selector)?.select(self))
+    }
+
+    /// Returns the first element below this one matching a CSS selector.
+    pub fn select_first(&self, selector: &str) -> Result<Option<&HtmlElement<'a>>, Herr> {
+        Ok(Selector::parse(selector)?.select_first(self))
+    }
+
+    /// Returns every element below this one matching an already parsed Selector, in document order.
+    pub fn select_with(&self, selector: &Selector) -> Vec<&HtmlElement<'a>> {
+        selector.select(self)
+    }
+
+    /// Returns the first element below this one matching an already parsed Selector.
+    pub fn select_first_with(&self, selector: &Selector) -> Option<&HtmlElement<'a>> {
+        selector.select_first(self)
+    }
+
+    /// Looks for the element, this one included, bearing the given id.
+    pub fn find_by_id(&self, id: &str) -> Option<&HtmlElement<'a>> {
+        if self.attribute("id") == Some(id) {
+            return Some(self);
+        }
+        self.inner_elements
+            .iter()
+            .find_map(|inner_element| inner_element.find_by_id(id))
+    }
+
+    /// Iterates over the inner elements having the given class, but not over their own.
+    pub fn children_with_class<'r>(&'r self, class: &'r str) -> impl Iterator<Item = &'r HtmlElement<'a>> {
+        self.inner_elements
+            .iter()
+            .filter(move |inner_element| inner_element.has_class(class))
+    }
+
+    /// Looks for the first element of a given kind, this one included, depth first.
+    fn first(&self, kind: &str) -> Option<&HtmlElement<'a>> {
+        if self.kind == kind {
+            return Some(self);
+        }
+        self.inner_elements
+            .iter()
+            .find_map(|inner_element| inner_element.first(kind))
+    }
+
+    /// Takes the first element of a given kind out of the tree, handing the whole tree back if there's none.
+    fn into_first(mut self, kind: &str) -> HtmlElement<'a> {
+        if self.kind == kind {
+            return self;
+        }
+        match self
             .inner_elements
-            .iter_mut()
-            .filter(|inner_element| inner_element.kind != "div")
-            .map(|inner_element| {
-                if let Some(r#type) = inner_element.attributes.get("class") {
-                    let name = inner_element.content[0].clone();
-                    match r#type.as_str() {
-                        "trait" => Fragment::Colored(Box::new(name), Color::Trait),
-                        "struct" => Fragment::Colored(Box::new(name), Color::Struct),
-                        "enum" => Fragment::Colored(Box::new(name), Color::Enum),
-                        "macro" => Fragment::Colored(Box::new(name), Color::Macro),
-                        "primitive" => Fragment::Colored(Box::new(name), Color::Primitive),
-                        "fn" => Fragment::Colored(Box::new(name), Color::Method),
-                        "associatedtype" => {
-                            Fragment::Colored(Box::new(name), Color::AssociatedType)
-                        }
-                        _ => name,
-                    }
-                } else if &inner_element.kind == "code" {
-                    Fragment::Code(Box::new(inner_element.content[0].clone()))
-                } else if !&inner_element.inner_elements.is_empty() {
-                    match inner_element.inner_elements[0].kind.as_str() {
-                        // a containing code
-                        "code" => Fragment::Code(Box::new(
-                            inner_element.inner_elements[0].content[0].clone(),
-                        )),
-                        // em containing a
-                        "a" | _ => inner_element.inner_elements[0].content[0].clone(),
+            .iter()
+            .position(|inner_element| inner_element.first(kind).is_some())
+        {
+            Some(index) => self.inner_elements.swap_remove(index).into_first(kind),
+            None => self,
+        }
+    }
+
+    /// Gathers every piece of raw text found within the element, in order.
+    ///
+    /// Borrows the text whenever it comes in a single piece.
+    pub fn text(&self) -> Cow<'a, str> {
+        let mut children = self.children();
+        match (children.next(), children.next()) {
+            (None, _) => Cow::Borrowed(""),
+            (Some(Node::Content(Fragment::Raw(text))), None) => text.clone(),
+            (Some(Node::Element(element)), None) => element.text(),
+            _ => {
+                let mut text = String::new();
+                self.write_text(&mut text);
+                Cow::Owned(text)
+            }
+        }
+    }
+
+    fn write_text(&self, text: &mut String) {
+        for child in self.children() {
+            match child {
+                Node::Content(fragment) => text.push_str(fragment.raw_content().unwrap_or_default()),
+                Node::Element(element) => element.write_text(text),
+            }
+        }
+    }
+
+    /// Returns the inner block's full content as fragments through zipping recursively.
+    pub fn zip_content(&self) -> Vec<Fragment<'a>> {
+        self.children().fold(Vec::new(), |mut zipped_content, child| {
+            match child {
+                Node::Content(raw_content) => zipped_content.push(raw_content.clone()),
+                // where clauses
+                Node::Element(inner_element) if inner_element.kind == "div" => {
+                    if inner_element.has_class("where") {
+                        zipped_content.append(&mut inner_element.zip_content());
                     }
-                } else {
-                    inner_element.content[0].clone()
                 }
-            });
-        let mut zipped_content = content.iter().zip(inner_elements_content).fold(
-            Vec::new(),
-            |mut zipped_content, (raw_content, inner_content)| {
-                zipped_content.push(raw_content.clone());
-                zipped_content.push(inner_content);
-
-                zipped_content
-            },
-        );
-        // where clauses
-        if let Some(mut last_inner_element) = self.inner_elements.pop()
-            && let Some(class) = last_inner_element.attributes.get("class")
-            && class == "where"
+                // the § links next to headings
+                Node::Element(inner_element)
+                    if inner_element.has_class("anchor") || inner_element.has_class("doc-anchor") => {}
+                Node::Element(inner_element) => zipped_content.push(inner_element.zip_inner()),
+            }
+            zipped_content
+        })
+    }
+
+    /// Turns an inline inner element into a single Fragment, colored after its class if any.
+    ///
+    /// Links which aren't colored keep where they lead.
+    pub fn zip_inner(&self) -> Fragment<'a> {
+        if self.kind == "strong" || self.kind == "b" {
+            Fragment::Bold(self.zip_content())
+        } else if self.kind == "a"
+            && self.attribute("class").is_none()
+            && let Some((_, href)) = self.attributes.iter().find(|(name, href)| name == "href" && !href.is_empty())
         {
-            // adding trailing content before the where clause
-            if content.len() > self.inner_elements.len()
-                && let Some(last_piece_of_raw_content) = content.clone().pop()
-            {
-                zipped_content.push(last_piece_of_raw_content);
+            let content = match self.inner_elements.first() {
+                Some(code) if code.kind == "code" => Fragment::Code(Box::new(Fragment::Raw(code.text()))),
+                _ => Fragment::Raw(self.text()),
+            };
+            Fragment::Link {
+                content: Box::new(content),
+                href: href.clone(),
+            }
+        } else if let Some(r#type) = self.attribute("class") {
+            let name = Fragment::Raw(self.text());
+            match r#type {
+                "trait" => Fragment::Colored(Box::new(name), Color::Trait),
+                "struct" => Fragment::Colored(Box::new(name), Color::Struct),
+                "enum" => Fragment::Colored(Box::new(name), Color::Enum),
+                "macro" => Fragment::Colored(Box::new(name), Color::Macro),
+                "primitive" => Fragment::Colored(Box::new(name), Color::Primitive),
+                "fn" => Fragment::Colored(Box::new(name), Color::Method),
+                "associatedtype" => Fragment::Colored(Box::new(name), Color::AssociatedType),
+                _ => name,
             }
-            zipped_content.append(&mut last_inner_element.zip_content());
-            return zipped_content
-                .iter_mut()
-                .map(|fragment| fragment.clone().un_escape_content())
-                .collect::<Vec<Fragment>>();
-        }
-        // zipping vecs of unequal sizes leaves content behind
-        if content.len() > self.inner_elements.len()
-            && let Some(last_piece_of_raw_content) = content.clone().pop()
+        } else if self.kind == "code" {
+            Fragment::Code(Box::new(Fragment::Raw(self.text())))
+        } else if let Some(inner_element) = self.inner_elements.first()
+            && inner_element.kind == "code"
         {
-            zipped_content.push(last_piece_of_raw_content);
+            // a containing code
+            Fragment::Code(Box::new(Fragment::Raw(inner_element.text())))
+        } else {
+            Fragment::Raw(self.text())
         }
-
-        zipped_content
-            .iter_mut()
-            .map(|fragment| fragment.un_escape_content())
-            .collect::<Vec<Fragment>>()
     }
 
     /// Returns the content of a CodeBlock as a single Raw Fragment.
-    pub fn zip_code(&mut self) -> Box<Fragment> {
-        let spans = self
-            .inner_elements
-            .iter()
-            .map(|span| span.content[0].clone());
-        let last_span = spans.clone().last().unwrap();
-        let content = &self.content;
+    pub fn zip_code(&self) -> Box<Fragment<'a>> {
+        Box::new(Fragment::Raw(self.text()))
+    }
+
+    /// Returns every attribute of the element, in the order they come in.
+    pub fn attributes(&self) -> &[(Cow<'a, str>, Cow<'a, str>)] {
+        &self.attributes
+    }
+
+    /// Serializes the element and all of its inner elements back into HTML.
+    ///
+    /// Comments are left out, and every non-void element gets its end tag : parsing the output
+    /// back yields an element equal to this one.
+    /// The nameless root element of a document only serializes its children.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        self.write_html(&mut html);
 
-        let mut zipped_content =
-            spans
-                .zip(content)
-                .fold(Vec::new(), |mut zipped_content, (span, content)| {
-                    zipped_content.push(span);
-                    zipped_content.push(content.clone());
+        html
+    }
 
-                    zipped_content
-                });
-        if self.inner_elements.len() > content.len() {
-            zipped_content.push(last_span);
+    fn write_html(&self, html: &mut String) {
+        if !self.kind.is_empty() {
+            html.push('<');
+            html.push_str(&self.kind);
+            for (name, value) in &self.attributes {
+                html.push(' ');
+                html.push_str(name);
+                if !value.is_empty() {
+                    html.push_str("=\"");
+                    escape(value, true, html);
+                    html.push('"');
+                }
+            }
+            html.push('>');
+            if is_void(&self.kind) {
+                return;
+            }
         }
+        for child in self.children() {
+            match child {
+                Node::Content(raw_content) if is_raw_text(&self.kind) => {
+                    html.push_str(raw_content.raw_content().unwrap_or_default())
+                }
+                Node::Content(raw_content) => escape(raw_content.raw_content().unwrap_or_default(), false, html),
+                Node::Element(inner_element) => inner_element.write_html(html),
+            }
+        }
+        if !self.kind.is_empty() {
+            html.push_str("</");
+            html.push_str(&self.kind);
+            html.push('>');
+        }
+    }
+}
 
-        Box::new(
-            zipped_content
-                .into_iter()
-                .reduce(|previous, next| {
-                    Fragment::Raw(format!("{}{}",
-                        previous.un_escape_content().raw_content(),
-                        next.un_escape_content().raw_content()
-                    ))
-                })
-                .unwrap(),
-        )
+/// Escapes text the way the HTML serialization algorithm does.
+fn escape(text: &str, in_attribute: bool, html: &mut String) {
+    for character in text.chars() {
+        match character {
+            '&' => html.push_str("&amp;"),
+            '\u{a0}' => html.push_str("&nbsp;"),
+            '"' if in_attribute => html.push_str("&quot;"),
+            '<' if !in_attribute => html.push_str("&lt;"),
+            '>' if !in_attribute => html.push_str("&gt;"),
+            _ => html.push(character),
+        }
+    }
+}
+
+/// Whether an element is an SVG or MathML one, for which self-closing tags are honoured.
+fn is_foreign(name: &str) -> bool {
+    matches!(name, "svg" | "math")
+}
+
+/// Finds the depth of the open element a start tag implicitly closes, such as a `<li>` closing the previous `<li>`.
+fn implied_end(bottom: &HtmlElement, open: &[HtmlElement], name: &str) -> Option<usize> {
+    // from the innermost open element down to the bottom one, along with their depth
+    let open = open
+        .iter()
+        .enumerate()
+        .map(|(index, element)| (index + 1, element))
+        .rev()
+        .chain(std::iter::once((0, bottom)));
+    let (closed, boundaries): (&[&str], &[&str]) = match name {
+        "li" if open.clone().any(|(_, element)| element.kind == "li") => (&["li"], &["ul", "ol"]),
+        "dt" | "dd" if open.clone().any(|(_, element)| element.kind == "dt" || element.kind == "dd") => {
+            (&["dt", "dd"], &["dl"])
+        }
+        "option" => (&["option"], &["select", "optgroup"]),
+        "tr" => (&["tr"], &["table", "thead", "tbody", "tfoot"]),
+        "td" | "th" => (&["td", "th"], &["tr", "table"]),
+        "address" | "article" | "aside" | "blockquote" | "details" | "dialog" | "dd" | "div" | "dl"
+        | "dt" | "fieldset" | "figcaption" | "figure" | "footer" | "form" | "h1" | "h2" | "h3"
+        | "h4" | "h5" | "h6" | "header" | "hgroup" | "hr" | "li" | "main" | "menu" | "nav" | "ol"
+        | "p" | "pre" | "section" | "summary" | "table" | "ul" => (&["p"], &["button"]),
+        _ => return None,
+    };
+
+    open.take_while(|(_, element)| {
+        !boundaries.contains(&element.kind.as_ref())
+            && !matches!(element.kind.as_ref(), "" | "html" | "table" | "td" | "th" | "caption" | "template")
+    })
+    .find(|(_, element)| closed.contains(&element.kind.as_ref()))
+    .map(|(depth, _)| depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{HtmlElement, Node};
+    use crate::helper_types::fragment::{Color, Fragment};
+
+    #[test]
+    fn parse_tree() {
+        let html = r#"<ul><li title="Vec<T>">one<li>two <a class=trait href='#'>Iterator</a>!</ul><p>a<p>b"#;
+        let document = HtmlElement::parse_document(html);
+        let kinds = document
+            .inner_elements
+            .iter()
+            .map(|element| element.kind.as_ref())
+            .collect::<Vec<&str>>();
+        assert_eq!(kinds, ["ul", "p", "p"]);
+        let list = &document.inner_elements[0];
+        assert_eq!(list.inner_elements.len(), 2);
+        assert_eq!(list.inner_elements[0].text(), "one");
+        assert_eq!(
+            list.inner_elements[1].zip_content(),
+            [
+                Fragment::Raw(Cow::from("two ")),
+                Fragment::Colored(Box::new(Fragment::Raw(Cow::from("Iterator"))), Color::Trait),
+                Fragment::Raw(Cow::from("!")),
+            ]
+        );
+        assert!(matches!(list.inner_elements[1].children().nth(1), Some(Node::Element(element)) if element.kind == "a"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let html = concat!(
+            "<!DOCTYPE html><html><head><title>Vec&lt;T&gt;</title><script>if (a<b && c) {}</script></head>",
+            "<body class=rustdoc><!-- skipped --><details class='toggle' open><summary title=\"a &quot;b&quot; &amp; c\">",
+            "x&nbsp;&gt;&nbsp;y</summary><ul><li>one<li>two<br></ul><p>a<div>b</div></details></body></html>",
+        );
+        let document = HtmlElement::parse_document(html);
+        let serialized = document.to_html();
+        assert_eq!(HtmlElement::parse_document(&serialized), document);
+        assert_eq!(serialized, HtmlElement::parse_document(&serialized).to_html());
+        assert!(serialized.contains(r#"<summary title="a &quot;b&quot; &amp; c">x&nbsp;&gt;&nbsp;y</summary>"#));
+        assert!(serialized.contains("<script>if (a<b && c) {}</script>"));
+        assert!(serialized.contains("<ul><li>one</li><li>two<br></li></ul><p>a</p><div>b</div>"));
+    }
+
+    #[test]
+    fn parse_advances() {
+        let mut html = "<details open><summary>a</summary><br><svg><path d=\"\"/></svg></details> <p>next</p>";
+        let details = HtmlElement::parse(&mut html);
+        assert_eq!(details.kind, "details");
+        assert_eq!(details.inner_elements.len(), 3);
+        assert_eq!(details.inner_elements[2].inner_elements[0].kind, "path");
+        assert_eq!(html, "<p>next</p>");
     }
 }
@@ -1,22 +1,204 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{
+        self,
+        Display,
+        Formatter
+    },
+};
+
+use crate::{
+    errors::Herr,
+    main_content::{
+        anchor,
+        code_header,
+        TraitImplKind,
+        TraitImplementation
+    },
+    version::Adapter,
+};
+
 use super::{
 	description::Description,
-	fragment::Fragment,
-	html_element::HtmlElement
+	fragment::{
+		code_block,
+		inline_code,
+		write_plain,
+		Fragment
+	},
+	html_element::HtmlElement,
+	scraped_example::ScrapedExample,
+	selector::selector,
 };
 
 /// Represents one of several possible methods.
 #[derive(Debug)]
-pub struct Method {
-    pub signature: Vec<Fragment>,
-    pub description: Description,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Method<'a> {
+    /// Such as `method.map`, or `tymethod.next` for a trait's required methods.
+    pub id: Option<Cow<'a, str>>,
+    pub signature: Vec<Fragment<'a>>,
+    pub description: Description<'a>,
+    /// The return type rustdoc has notable traits for, such as `Iter<'_, T>`, as keyed in the page's `notable-traits-data`.
+    pub notable_type: Option<String>,
+    /// Filled in once the whole page is read, rustdoc keeping them at its very end.
+    pub notable_traits: Vec<NotableTrait>,
+    /// Its "Examples found in repository".
+    pub examples: Vec<ScrapedExample>,
+    /// Where its `Source` link leads, such as `../../src/core/result.rs.html#779-784`.
+    pub source: Option<String>,
+}
+
+/// A trait worth knowing a return type implements, such as `Iterator` for `Iter<'a, T>`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotableTrait {
+    /// Such as `Iterator`.
+    pub trait_path: String,
+    /// The type implementing it, such as `Iter<'a, T>`.
+    pub self_type: String,
+    /// Such as `Item = &'a T`.
+    pub associated_types: Vec<String>,
+}
+
+impl<'a> Method<'a> {
+    /// Parses the one method, be it a toggle or a lone section, into its signature and description.
+    pub fn parse(method: &HtmlElement<'a>, adapter: &Adapter) -> Result<Self, Herr> {
+        let mut signature = code_header(method, adapter)?;
+        // the notable traits' tooltip, filled in later on
+        signature.retain(|fragment| fragment.raw_content() != Some("ⓘ"));
+
+        Ok(Self {
+            id: anchor(method),
+            signature,
+            // the scraped examples' list being a docblock too
+            description: method
+                .select_first_with(selector!(".docblock:not(.scraped-example-list)"))
+                .map(Description::parse)
+                .unwrap_or_default(),
+            notable_type: method
+                .select_first_with(selector!("[data-notable-ty]"))
+                .and_then(|tooltip| tooltip.attribute("data-notable-ty"))
+                .map(String::from),
+            notable_traits: Vec::new(),
+            examples: ScrapedExample::parse_all(method),
+            source: method
+                .select_first_with(selector!("a.src, a.srclink"))
+                .and_then(|link| link.attribute("href"))
+                .map(String::from),
+        })
+    }
+}
+
+impl NotableTrait {
+    /// Reads the JSON of the page's `<script id="notable-traits-data">`, as a PageStream tells it, each return type's popup being keyed by the type.
+    pub fn parse_data(data: &str) -> HashMap<String, Vec<NotableTrait>> {
+        serde_json::from_str::<HashMap<String, String>>(data)
+            .into_iter()
+            .flatten()
+            .map(|(r#type, popup)| (r#type, NotableTrait::parse_popup(&popup)))
+            .collect()
+    }
+
+    /// Parses one popup, `Notable traits for Iter<'a, T>` followed by an impl block's header and its associated types.
+    fn parse_popup(popup: &str) -> Vec<NotableTrait> {
+        let document = HtmlElement::parse_document(popup);
+        let mut traits: Vec<NotableTrait> = Vec::new();
+        for line in document.select_with(selector!("code > .where")) {
+            let text = line.text();
+            let text = text.trim();
+            match (text.strip_prefix("type "), traits.last_mut()) {
+                (Some(associated_type), Some(notable)) => notable
+                    .associated_types
+                    .push(String::from(associated_type.trim_end_matches(';'))),
+                _ if text.starts_with("impl") => {
+                    let implementation = TraitImplementation::new(None, Fragment::Raw(Cow::Borrowed(text)), TraitImplKind::Trait, Vec::new());
+                    traits.push(NotableTrait {
+                        trait_path: implementation.trait_path,
+                        self_type: implementation.self_type,
+                        associated_types: Vec::new(),
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        traits
+    }
+}
+
+impl Method<'_> {
+    /// Renders the method as Markdown, its signature in a block of Rust code followed by its description.
+    pub fn to_markdown(&self) -> String {
+        let signature = self.signature.iter().map(Fragment::to_string).collect::<String>();
+        let mut markdown = code_block(&signature, "rust");
+        if !self.notable_traits.is_empty() {
+            markdown += &format!("Returns {}.\n\n", inline_code(&notable_traits(&self.notable_traits)));
+        }
+        markdown += &self.description.to_markdown();
+        if !self.examples.is_empty() {
+            let examples = self.examples.iter().map(ScrapedExample::to_markdown).collect::<Vec<String>>();
+            markdown += &format!("\n\n#### Examples found in repository\n\n{}", examples.join("\n\n"));
+        }
+
+        String::from(markdown.trim())
+    }
+}
+
+/// Plain text, the signature on top of the description.
+impl Display for Method<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write_plain(f, &self.signature)?;
+        if !self.notable_traits.is_empty() {
+            write!(f, "\nReturns {}", notable_traits(&self.notable_traits))?;
+        }
+        if !self.description.is_empty() {
+            write!(f, "\n{}", self.description)?;
+        }
+
+        Ok(())
+    }
 }
 
-impl Method {
-    /// Parses the one method into its signature and description.
-    pub fn parse(data: &str) -> Self {
-        Self {
-            signature: HtmlElement::extract("h4", data).zip_content(),
-            description: Description::parse(data),
+/// Such as `Iterator<Item = &'a T>`.
+impl Display for NotableTrait {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.trait_path)?;
+        if !self.associated_types.is_empty() {
+            write!(f, "<{}>", self.associated_types.join(", "))?;
         }
+
+        Ok(())
+    }
+}
+
+/// The traits a return type implements, as a bound would name them.
+fn notable_traits(traits: &[NotableTrait]) -> String {
+    traits.iter().map(NotableTrait::to_string).collect::<Vec<String>>().join(" + ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::main_content::test_pages::{
+        rustdoc_page,
+        section_content,
+    };
+
+    #[test]
+    fn notable_traits() {
+        let page = rustdoc_page("struct", "\
+            <h2 class=\"section-header\">Implementations</h2><div id=\"implementations-list\">\
+            <details class=\"toggle implementors-toggle\"><summary><section class=\"impl\"><h3 class=\"code-header\">impl S</h3></section></summary>\
+            <div class=\"impl-items\"><section id=\"method.iter\" class=\"method\"><h4 class=\"code-header\">pub fn iter(&amp;self) -&gt; Iter&lt;'_, T&gt; \
+            <a href=\"#\" class=\"tooltip\" data-notable-ty=\"Iter&lt;&#39;_, T&gt;\">ⓘ</a></h4></section></div></details></div>\
+            <script type=\"text/json\" id=\"notable-traits-data\">{\"Iter<'_, T>\":\"<h3>Notable traits for <code>Iter&lt;'a, T&gt;</code></h3>\
+            <pre><code><div class=\\\"where\\\">impl&lt;'a, T&gt; <a class=\\\"trait\\\">Iterator</a> for Iter&lt;'a, T&gt;</div>\
+            <div class=\\\"where\\\">    type Item = &amp;'a T;</div></code></pre>\"}</script>");
+        let implementations = section_content!(page, 0, Implementations);
+        let method = &implementations[0].methods[0];
+        assert_eq!(method.notable_type.as_deref(), Some("Iter<'_, T>"));
+        assert_eq!(method.notable_traits[0].self_type, "Iter<'a, T>");
+        assert_eq!(method.to_string(), "pub fn iter(&self) -> Iter<'_, T> \nReturns Iterator<Item = &'a T>");
     }
 }
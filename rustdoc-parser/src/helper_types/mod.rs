@@ -1,5 +1,9 @@
 pub mod description;
+pub mod entity;
 pub mod fragment;
 pub mod html_element;
 pub mod method;
+pub mod scraped_example;
+pub mod selector;
 pub mod tag;
+pub mod tokenizer;
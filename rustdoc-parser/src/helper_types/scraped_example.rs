@@ -0,0 +1,136 @@
+use std::fmt::{
+    self,
+    Display,
+    Formatter
+};
+
+use serde_json::Value;
+
+use super::{
+    fragment::code_block,
+    html_element::HtmlElement,
+    selector::selector,
+};
+
+/// One of the call sites rustdoc found in the crate's examples, when documenting with `-Zrustdoc-scrape-examples`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScrapedExample {
+    /// Such as `examples/catch.rs`.
+    pub file: String,
+    /// The source page, such as `../src/catch/catch.rs.html#10-12`.
+    pub target: Option<String>,
+    /// The first and last lines of the snippet within its file, both included.
+    pub lines: (usize, usize),
+    /// The calls' lines, both ends included, the first call being the one the title links to.
+    pub highlighted: Vec<(usize, usize)>,
+    pub code: String,
+}
+
+impl ScrapedExample {
+    /// Parses an item's "Examples found in repository", the ones behind "More examples" included.
+    ///
+    /// Examples which are merely linked to, with no code shown, are left out.
+    pub fn parse_all(item: &HtmlElement) -> Vec<Self> {
+        item.select_with(selector!(".scraped-example-list .scraped-example"))
+            .into_iter()
+            .filter_map(ScrapedExample::parse)
+            .collect()
+    }
+
+    /// Parses a single `.scraped-example`, its title followed by its numbered code.
+    fn parse(example: &HtmlElement) -> Option<Self> {
+        let title = example.select_first_with(selector!(".scraped-example-title"))?;
+        let link = title.select_first_with(selector!("a"));
+        let target = link.and_then(|link| link.attribute("href")).map(String::from);
+        let text = title.text();
+        let file = text.split(" (").next().unwrap_or_default().trim();
+        let code = example.select_first_with(selector!("pre.rust"))?.text();
+        let code = code.trim_matches('\n');
+        // each location being `[[first, last], url, title]`, lines counting from the snippet's start
+        let locations = example
+            .attribute("data-locs")
+            .and_then(|locations| serde_json::from_str::<Vec<Value>>(locations).ok())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|location| Some((location[0][0].as_u64()? as usize, location[0][1].as_u64()? as usize)))
+            .collect::<Vec<(usize, usize)>>();
+        // the line numbers, or else the first call's line as the title's link tells it
+        let first_line = example
+            .select_first_with(selector!(".src-line-numbers, .example-line-numbers, .line-numbers"))
+            .and_then(|numbers| numbers.text().split_whitespace().next()?.parse::<usize>().ok())
+            .or_else(|| {
+                let line = target.as_deref()?.rsplit_once('#')?.1.split('-').next()?.parse::<usize>().ok()?;
+                line.checked_sub(locations.first()?.0)
+            })
+            .unwrap_or(1);
+
+        Some(ScrapedExample {
+            file: String::from(file),
+            target,
+            lines: (first_line, first_line + code.lines().count().max(1) - 1),
+            highlighted: locations
+                .into_iter()
+                .map(|(first, last)| (first_line + first, first_line + last))
+                .collect(),
+            code: String::from(code),
+        })
+    }
+
+    /// Renders the example as Markdown, its file and lines on top of a block of Rust code.
+    pub fn to_markdown(&self) -> String {
+        format!("`{}` ({})\n\n{}", self.file, self.line_range(), code_block(&self.code, "rust"))
+    }
+
+    /// Such as `lines 10-24`.
+    fn line_range(&self) -> String {
+        match self.lines {
+            (first, last) if first == last => format!("line {}", first),
+            (first, last) => format!("lines {}-{}", first, last),
+        }
+    }
+}
+
+/// Plain text, the file and lines on top of the code.
+impl Display for ScrapedExample {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} ({})\n{}", self.file, self.line_range(), self.code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScrapedExample;
+    use crate::main_content::test_pages::{
+        rustdoc_page,
+        section_content,
+    };
+
+    #[test]
+    fn scraped_examples() {
+        let page = rustdoc_page("struct", "\
+            <h2 class=\"section-header\">Implementations</h2><div id=\"implementations-list\">\
+            <details class=\"toggle implementors-toggle\"><summary><section class=\"impl\"><h3 class=\"code-header\">impl S</h3></section></summary>\
+            <div class=\"impl-items\"><details class=\"toggle method-toggle\"><summary><section id=\"method.new\" class=\"method\"><h4 class=\"code-header\">pub fn new() -&gt; S</h4></section></summary>\
+            <div class=\"docblock scraped-example-list\"><span></span><h5 id=\"scraped-examples\"><a href=\"#scraped-examples\">Examples found in repository</a></h5>\
+            <div class=\"scraped-example\" data-locs=\"[[[1,2],&quot;../src/shapes/circle.rs.html#11-12&quot;,&quot;lines 11-12&quot;]]\">\
+            <div class=\"scraped-example-title\">examples/circle.rs (<a href=\"../src/shapes/circle.rs.html#11-12\">lines 11-12</a>)</div>\
+            <div class=\"example-wrap\"><pre class=\"rust\"><code>fn main() {\n    let s = <span class=\"highlight focus\">S::new(\n    )</span>;\n}</code></pre></div></div>\
+            <details class=\"toggle more-examples-toggle\"><summary class=\"hideme\"><span>More examples</span></summary>\
+            <div class=\"example-links\">Additional examples can be found in:<br><ul><li><a href=\"../src/shapes/square.rs.html\">examples/square.rs</a></li></ul></div></details>\
+            </div></details></div></details></div>");
+        let implementations = section_content!(page, 0, Implementations);
+        let method = &implementations[0].methods[0];
+        assert!(method.description.is_empty());
+        assert_eq!(
+            method.examples,
+            [ScrapedExample {
+                file: String::from("examples/circle.rs"),
+                target: Some(String::from("../src/shapes/circle.rs.html#11-12")),
+                lines: (10, 13),
+                highlighted: Vec::from([(11, 12)]),
+                code: String::from("fn main() {\n    let s = S::new(\n    );\n}"),
+            }]
+        );
+    }
+}
@@ -0,0 +1,462 @@
+use crate::errors::Herr;
+
+use super::html_element::HtmlElement;
+
+/// Parses a literal selector list once, on first use, into a `&'static Selector`.
+///
+/// # Panics
+///
+/// Panics if the selector is invalid, which the tests going through it would tell.
+macro_rules! selector {
+    ($selectors:literal) => {{
+        static SELECTOR: std::sync::LazyLock<crate::helper_types::selector::Selector> = std::sync::LazyLock::new(|| {
+            crate::helper_types::selector::Selector::parse($selectors)
+                .unwrap_or_else(|err| panic!("Invalid selector `{}` : {}", $selectors, err))
+        });
+        &*SELECTOR
+    }};
+}
+pub(crate) use selector;
+
+/// A list of CSS selectors, such as `.impl-items > details.method-toggle h4.code-header, h3`.
+///
+/// Supports type, universal, class, id and attribute selectors, the `:first-child`, `:last-child`
+/// and `:not()` pseudo-classes, as well as the four combinators.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selector(Vec<Complex>);
+
+/// A chain of compound selectors, such as `div.impl-items > details`.
+#[derive(Clone, Debug, PartialEq)]
+struct Complex {
+    first: Compound,
+    rest: Vec<(Combinator, Compound)>,
+}
+
+/// How two compound selectors relate to one another.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Combinator {
+    /// `a b`
+    Descendant,
+    /// `a > b`
+    Child,
+    /// `a + b`
+    NextSibling,
+    /// `a ~ b`
+    SubsequentSibling,
+}
+
+/// Every condition a single element must meet, such as `details.toggle[open]`.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Compound {
+    kind: Option<String>,
+    conditions: Vec<Condition>,
+}
+
+/// A single condition upon an element.
+#[derive(Clone, Debug, PartialEq)]
+enum Condition {
+    Attribute(String, Option<(Operator, String)>),
+    Class(String),
+    FirstChild,
+    Id(String),
+    LastChild,
+    Not(Compound),
+}
+
+/// How an attribute's value is compared to the expected one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    /// `[a=b]`
+    Equals,
+    /// `[a~=b]`, b being one of the whitespace separated words
+    Includes,
+    /// `[a|=b]`, b or b followed by a dash
+    DashMatch,
+    /// `[a^=b]`
+    Prefix,
+    /// `[a$=b]`
+    Suffix,
+    /// `[a*=b]`
+    Substring,
+}
+
+/// An element along with its parent, so that its siblings may be reached.
+#[derive(Clone, Copy)]
+struct Candidate<'r, 'a> {
+    parent: &'r HtmlElement<'a>,
+    index: usize,
+}
+
+impl<'r, 'a> Candidate<'r, 'a> {
+    fn element(&self) -> &'r HtmlElement<'a> {
+        &self.parent.inner_elements[self.index]
+    }
+
+    /// The sibling right before this one, if any.
+    fn previous(&self) -> Option<Candidate<'r, 'a>> {
+        self.index.checked_sub(1).map(|index| Candidate {
+            parent: self.parent,
+            index,
+        })
+    }
+}
+
+impl Selector {
+    /// Parses a list of comma separated selectors.
+    pub fn parse(selectors: &str) -> Result<Self, Herr> {
+        let mut parser = Parser {
+            data: selectors,
+            position: 0,
+        };
+        let mut list = Vec::from([parser.complex()?]);
+        while parser.eat(',') {
+            list.push(parser.complex()?);
+        }
+        parser.skip_whitespace();
+        if parser.position < selectors.len() {
+            return Err(Herr::Parsing("Unexpected character in selector"));
+        }
+
+        Ok(Selector(list))
+    }
+
+    /// Looks for every element below the scope which matches any of the selectors, in document order.
+    pub fn select<'r, 'a>(&self, scope: &'r HtmlElement<'a>) -> Vec<&'r HtmlElement<'a>> {
+        let mut matched = Vec::new();
+        self.walk(scope, &mut Vec::new(), &mut matched, usize::MAX);
+
+        matched
+    }
+
+    /// Looks for the first element below the scope which matches any of the selectors, stopping right there.
+    pub fn select_first<'r, 'a>(&self, scope: &'r HtmlElement<'a>) -> Option<&'r HtmlElement<'a>> {
+        let mut matched = Vec::new();
+        self.walk(scope, &mut Vec::new(), &mut matched, 1);
+
+        matched.pop()
+    }
+
+    /// Goes through the elements below the parent depth first, until enough of them matched.
+    ///
+    /// Keeps track of the ancestors in between, for the combinators to be matched right to left.
+    fn walk<'r, 'a>(
+        &self,
+        parent: &'r HtmlElement<'a>,
+        ancestors: &mut Vec<Candidate<'r, 'a>>,
+        matched: &mut Vec<&'r HtmlElement<'a>>,
+        limit: usize,
+    ) {
+        for index in 0..parent.inner_elements.len() {
+            if matched.len() >= limit {
+                return;
+            }
+            let candidate = Candidate { parent, index };
+            if self
+                .0
+                .iter()
+                .any(|complex| complex.matches(complex.rest.len(), candidate, ancestors))
+            {
+                matched.push(candidate.element());
+            }
+            ancestors.push(candidate);
+            self.walk(candidate.element(), ancestors, matched, limit);
+            ancestors.pop();
+        }
+    }
+}
+
+impl Complex {
+    /// The compound selector at the given depth, the first one being at 0.
+    fn compound(&self, depth: usize) -> &Compound {
+        match depth {
+            0 => &self.first,
+            depth => &self.rest[depth - 1].1,
+        }
+    }
+
+    /// Whether the candidate matches the compound selector at the given depth, along with all of those before it.
+    fn matches(&self, depth: usize, candidate: Candidate, ancestors: &[Candidate]) -> bool {
+        if !self.compound(depth).matches(&candidate) {
+            return false;
+        }
+        if depth == 0 {
+            return true;
+        }
+        match self.rest[depth - 1].0 {
+            Combinator::Descendant => (0..ancestors.len())
+                .rev()
+                .any(|index| self.matches(depth - 1, ancestors[index], &ancestors[..index])),
+            Combinator::Child => ancestors
+                .split_last()
+                .is_some_and(|(parent, ancestors)| self.matches(depth - 1, *parent, ancestors)),
+            Combinator::NextSibling => candidate
+                .previous()
+                .is_some_and(|sibling| self.matches(depth - 1, sibling, ancestors)),
+            Combinator::SubsequentSibling => std::iter::successors(candidate.previous(), Candidate::previous)
+                .any(|sibling| self.matches(depth - 1, sibling, ancestors)),
+        }
+    }
+}
+
+impl Compound {
+    fn matches(&self, candidate: &Candidate) -> bool {
+        let element = candidate.element();
+        self.kind.as_ref().is_none_or(|kind| *kind == element.kind)
+            && self.conditions.iter().all(|condition| match condition {
+                Condition::Attribute(name, None) => element.attribute(name).is_some(),
+                Condition::Attribute(name, Some((operator, expected))) => element
+                    .attribute(name)
+                    .is_some_and(|value| operator.compare(value, expected)),
+                Condition::Class(class) => element.has_class(class),
+                Condition::FirstChild => candidate.index == 0,
+                Condition::Id(id) => element.attribute("id") == Some(id.as_str()),
+                Condition::LastChild => candidate.index + 1 == candidate.parent.inner_elements.len(),
+                Condition::Not(compound) => !compound.matches(candidate),
+            })
+    }
+}
+
+impl Operator {
+    fn compare(&self, value: &str, expected: &str) -> bool {
+        match self {
+            Operator::Equals => value == expected,
+            Operator::Includes => value.split_ascii_whitespace().any(|word| word == expected),
+            Operator::DashMatch => {
+                value == expected
+                    || value
+                        .strip_prefix(expected)
+                        .is_some_and(|rest| rest.starts_with('-'))
+            }
+            Operator::Prefix => !expected.is_empty() && value.starts_with(expected),
+            Operator::Suffix => !expected.is_empty() && value.ends_with(expected),
+            Operator::Substring => !expected.is_empty() && value.contains(expected),
+        }
+    }
+}
+
+/// Walks through a selector's text.
+struct Parser<'a> {
+    data: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.data[self.position..].chars().next()
+    }
+
+    /// Skips CSS whitespace, which is ASCII only: any other character is left to fail the parsing.
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.position;
+        while self.peek().is_some_and(|c| matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0c')) {
+            self.position += 1;
+        }
+
+        self.position > start
+    }
+
+    /// Consumes the character, and any whitespace around it, if it comes next.
+    fn eat(&mut self, expected: char) -> bool {
+        let start = self.position;
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            self.skip_whitespace();
+            return true;
+        }
+        self.position = start;
+
+        false
+    }
+
+    /// Consumes the closing character, and any whitespace before it, if it comes next.
+    fn close(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            return true;
+        }
+
+        false
+    }
+
+    fn identifier(&mut self) -> Result<String, Herr> {
+        let start = self.position;
+        while let Some(c) = self.peek()
+            && (c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            self.position += c.len_utf8();
+        }
+        if self.position == start {
+            return Err(Herr::Parsing("Expected an identifier in selector"));
+        }
+
+        Ok(String::from(&self.data[start..self.position]))
+    }
+
+    fn complex(&mut self) -> Result<Complex, Herr> {
+        self.skip_whitespace();
+        let first = self.compound()?;
+        let mut rest = Vec::new();
+        loop {
+            let combinator = if self.eat('>') {
+                Combinator::Child
+            } else if self.eat('+') {
+                Combinator::NextSibling
+            } else if self.eat('~') {
+                Combinator::SubsequentSibling
+            } else if self.skip_whitespace() && self.peek().is_some_and(|c| c != ',') {
+                Combinator::Descendant
+            } else {
+                break;
+            };
+            rest.push((combinator, self.compound()?));
+        }
+
+        Ok(Complex { first, rest })
+    }
+
+    fn compound(&mut self) -> Result<Compound, Herr> {
+        let mut compound = Compound::default();
+        let mut universal = false;
+        match self.peek() {
+            Some('*') => {
+                self.position += 1;
+                universal = true;
+            }
+            Some(c) if c.is_alphabetic() => compound.kind = Some(self.identifier()?.to_ascii_lowercase()),
+            _ => (),
+        }
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.position += 1;
+                    compound.conditions.push(Condition::Class(self.identifier()?));
+                }
+                Some('#') => {
+                    self.position += 1;
+                    compound.conditions.push(Condition::Id(self.identifier()?));
+                }
+                Some('[') => {
+                    self.position += 1;
+                    compound.conditions.push(self.attribute()?);
+                }
+                Some(':') => {
+                    self.position += 1;
+                    compound.conditions.push(self.pseudo_class()?);
+                }
+                _ => break,
+            }
+        }
+        if !universal && compound.kind.is_none() && compound.conditions.is_empty() {
+            return Err(Herr::Parsing("Expected a selector"));
+        }
+
+        Ok(compound)
+    }
+
+    fn attribute(&mut self) -> Result<Condition, Herr> {
+        self.skip_whitespace();
+        let name = self.identifier()?.to_ascii_lowercase();
+        if self.close(']') {
+            return Ok(Condition::Attribute(name, None));
+        }
+        let operator = match self.peek() {
+            Some('=') => Operator::Equals,
+            Some('~') => Operator::Includes,
+            Some('|') => Operator::DashMatch,
+            Some('^') => Operator::Prefix,
+            Some('$') => Operator::Suffix,
+            Some('*') => Operator::Substring,
+            _ => return Err(Herr::Parsing("Expected an attribute operator in selector")),
+        };
+        self.position += 1;
+        if operator != Operator::Equals && !self.data[self.position..].starts_with('=') {
+            return Err(Herr::Parsing("Expected an attribute operator in selector"));
+        }
+        if operator != Operator::Equals {
+            self.position += 1;
+        }
+        self.skip_whitespace();
+        let value = match self.peek() {
+            Some(quote) if quote == '"' || quote == '\'' => {
+                let rest = &self.data[self.position + 1..];
+                let end = rest
+                    .find(quote)
+                    .ok_or(Herr::Parsing("Unterminated string in selector"))?;
+                self.position += end + 2;
+                String::from(&rest[..end])
+            }
+            _ => self.identifier()?,
+        };
+        if !self.close(']') {
+            return Err(Herr::Parsing("Expected a closing bracket in selector"));
+        }
+
+        Ok(Condition::Attribute(name, Some((operator, value))))
+    }
+
+    fn pseudo_class(&mut self) -> Result<Condition, Herr> {
+        match self.identifier()?.as_str() {
+            "first-child" => Ok(Condition::FirstChild),
+            "last-child" => Ok(Condition::LastChild),
+            "not" => {
+                if !self.eat('(') {
+                    return Err(Herr::Parsing("Expected an opening parenthesis in selector"));
+                }
+                let compound = self.compound()?;
+                if !self.close(')') {
+                    return Err(Herr::Parsing("Expected a closing parenthesis in selector"));
+                }
+                Ok(Condition::Not(compound))
+            }
+            _ => Err(Herr::Parsing("Unsupported pseudo-class in selector")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selector;
+    use std::borrow::Cow;
+
+    use crate::helper_types::html_element::HtmlElement;
+
+    const HTML: &str = r#"<div id="implementations-list"><details class="toggle implementors-toggle" open><summary><section id="impl-Result" class="impl"><h3 class="code-header">impl Result</h3></section></summary><div class="impl-items"><details class="toggle method-toggle" open><summary><section id="method.map" class="method"><h4 class="code-header">pub fn map</h4></section></summary><div class="docblock"><p>Maps.</p></div></details><section id="method.ok" class="method"><h4 class="code-header">pub fn ok</h4></section></div></details></div>"#;
+
+    fn texts(document: &HtmlElement, selector: &str) -> Vec<String> {
+        document
+            .select(selector)
+            .unwrap()
+            .iter()
+            .map(|element| element.text().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn select_combinators() {
+        let document = HtmlElement::parse_document(HTML);
+        assert_eq!(
+            texts(&document, ".impl-items > details.method-toggle h4.code-header"),
+            ["pub fn map"]
+        );
+        assert_eq!(texts(&document, "#implementations-list h4"), ["pub fn map", "pub fn ok"]);
+        assert_eq!(texts(&document, "summary + .docblock, h3"), ["impl Result", "Maps."]);
+        assert_eq!(texts(&document, "details ~ section:last-child"), ["pub fn ok"]);
+        assert_eq!(texts(&document, "section[id^=method]:not(.impl) > *"), ["pub fn map", "pub fn ok"]);
+        assert_eq!(texts(&document, "details[open][class~='method-toggle'] p"), ["Maps."]);
+    }
+
+    #[test]
+    fn lookups() {
+        let document = HtmlElement::parse_document(HTML);
+        assert_eq!(document.find_by_id("method.ok").map(|element| element.text()), Some(Cow::from("pub fn ok")));
+        let impl_items = document.select_first(".impl-items").unwrap().unwrap();
+        assert_eq!(impl_items.children_with_class("method").count(), 1);
+        assert!(document.select_first("div >").is_err());
+        assert!(Selector::parse("div >").is_err());
+        assert!(Selector::parse("[class=").is_err());
+        assert!(Selector::parse("div\u{a0}p").is_err());
+        assert!(Selector::parse("div >\u{2003}p").is_err());
+    }
+}
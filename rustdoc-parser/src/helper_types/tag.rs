@@ -1,11 +1,19 @@
-use std::collections::BTreeMap;
+use std::borrow::Cow;
 
-/// The full content of a tag.
+use super::tokenizer::{
+    Token,
+    Tokenizer
+};
+
+/// A tag's attributes, in the order they come in, repeated ones being dropped as browsers do.
+pub type Attributes<'a> = Vec<(Cow<'a, str>, Cow<'a, str>)>;
+
+/// The full content of a tag, borrowed from the document wherever possible.
 #[derive(Clone, Debug, PartialEq)]
-pub struct Tag {
-    pub name: String,
+pub struct Tag<'a> {
+    pub name: Cow<'a, str>,
     pub kind: TagKind,
-    pub attributes: BTreeMap<String, String>,
+    pub attributes: Attributes<'a>,
 }
 
 /// Any of a tag's kind.
@@ -16,55 +24,30 @@ pub enum TagKind {
     Void,
 }
 
-impl Tag {
-    /// Parses a new Tag.
-    pub fn parse(data: &str) -> Self {
-        let name;
-        let attributes;
-        if let Some((tag_name, content)) = data.split_once(' ') {
-            name = String::from(tag_name);
-            let mut attribs = Vec::new();
-            let mut values = Vec::new();
-            content
-                .split_terminator('"')
-                .enumerate()
-                .for_each(|(index, split)| {
-                    if index & 1 == 0 {
-                        attribs.push(split.trim_matches(|c| c == ' ' || c == '='));
-                    } else {
-                        values.push(split.trim());
-                    }
-                });
-            attributes = BTreeMap::from_iter(
-                attribs
-                    .iter()
-                    .zip(values)
-                    .map(|(key, value)| (String::from(*key), String::from(value))),
-            );
-        } else {
-            name = String::from(data);
-            attributes = BTreeMap::new();
-        }
-        match name.as_str() {
-            "area" | "base" | "br" | "col" | "embed" | "hr" | "img" | "input" | "link" | "meta"
-            | "param" | "source" | "track" | "wbr" => Tag {
-                name,
-                kind: TagKind::Void,
-                attributes,
+impl Tag<'_> {
+    /// Parses a new Tag out of the inside of its angle brackets, such as `a href="#"` or `/a`.
+    pub fn parse(data: &str) -> Tag<'static> {
+        match Tokenizer::new(&format!("<{}>", data)).next() {
+            Some(Token::StartTag { tag, .. }) | Some(Token::EndTag(tag)) => tag.into_owned(),
+            // not even a tag name, such as in `< a>`
+            _ => Tag {
+                name: Cow::Owned(String::from(data.trim_start_matches('/'))),
+                kind: if data.starts_with('/') { TagKind::Closing } else { TagKind::Opening },
+                attributes: Vec::new(),
             },
-            _ => {
-                let kind = if name.starts_with('/') {
-                    TagKind::Closing
-                } else {
-                    TagKind::Opening
-                };
+        }
+    }
 
-                Tag {
-                    name,
-                    kind,
-                    attributes,
-                }
-            }
+    /// Copies whatever the tag borrows, so that it may outlive its document.
+    pub fn into_owned(self) -> Tag<'static> {
+        Tag {
+            name: Cow::Owned(self.name.into_owned()),
+            kind: self.kind,
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|(name, value)| (Cow::Owned(name.into_owned()), Cow::Owned(value.into_owned())))
+                .collect(),
         }
     }
 }
@@ -72,24 +55,26 @@ impl Tag {
 #[cfg(test)]
 mod tests {
     use super::{Tag, TagKind};
-    use std::collections::BTreeMap;
+    use std::borrow::Cow;
+
     #[test]
     fn tag_build() {
         let data =
             r#"a href="emoclew\08:1.0.0.721\\:ptth" type="url Home" class="Url" open>!emocleW"#;
-        let attributes = BTreeMap::from([
+        let attributes = Vec::from([
             (
-                String::from("href"),
-                String::from(r#"emoclew\08:1.0.0.721\\:ptth"#),
+                Cow::from("href"),
+                Cow::from(r#"emoclew\08:1.0.0.721\\:ptth"#),
             ),
-            (String::from("type"), String::from("url Home")),
-            (String::from("class"), String::from("Url")),
+            (Cow::from("type"), Cow::from("url Home")),
+            (Cow::from("class"), Cow::from("Url")),
+            (Cow::from("open"), Cow::from("")),
         ]);
         let result = Tag {
-            name: String::from("a"),
+            name: Cow::from("a"),
             kind: TagKind::Opening,
             attributes,
         };
-        assert_eq!(result, Tag::build(data));
+        assert_eq!(result, Tag::parse(data));
     }
 }
@@ -0,0 +1,449 @@
+use std::borrow::Cow;
+
+use crate::errors::Location;
+
+use super::{
+    entity,
+    tag::{
+        Attributes,
+        Tag,
+        TagKind
+        }
+};
+
+/// A single piece of an HTML document, as cut out by the Tokenizer.
+///
+/// Borrows from the document, text only being copied when it holds character references to decode.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token<'a> {
+    Comment(&'a str),
+    Doctype(&'a str),
+    EndTag(Tag<'a>),
+    StartTag {
+        tag: Tag<'a>,
+        self_closing: bool,
+    },
+    Text(Cow<'a, str>),
+}
+
+/// The states a tag goes through while being tokenized, after the HTML Living Standard's.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    TagName,
+    BeforeAttributeName,
+    AttributeName,
+    AfterAttributeName,
+    BeforeAttributeValue,
+    AttributeValueQuoted(u8),
+    AttributeValueUnquoted,
+    AfterAttributeValueQuoted,
+    SelfClosingStartTag,
+}
+
+/// How the text following a start tag is to be read.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TextKind {
+    /// Character references are decoded, tags aren't recognized - `<title>`, `<textarea>`.
+    Escapable,
+    /// Read as is up to the matching end tag - `<script>`, `<style>`, ...
+    Raw,
+}
+
+/// Turns an HTML document into a stream of Tokens.
+///
+/// Never fails : just as browsers do, malformed markup is recovered from rather than rejected.
+pub struct Tokenizer<'a> {
+    html: &'a str,
+    position: usize,
+    raw_text: Option<(Cow<'a, str>, TextKind)>,
+    /// How far lines and columns were counted, which only ever goes forward.
+    counted: Location,
+}
+
+impl<'a> Tokenizer<'a> {
+    /// Builds a new Tokenizer, starting at the very beginning of the document.
+    pub fn new(html: &'a str) -> Self {
+        Tokenizer {
+            html,
+            position: 0,
+            raw_text: None,
+            counted: Location {
+                offset: 0,
+                line: 1,
+                column: 1,
+            },
+        }
+    }
+
+    /// Builds a new Tokenizer, starting at the given byte offset of the document, which must fall between two Tokens.
+    ///
+    /// Locations are still counted from the very beginning.
+    pub fn starting_at(html: &'a str, offset: usize) -> Self {
+        Tokenizer {
+            position: offset,
+            ..Tokenizer::new(html)
+        }
+    }
+
+    /// Byte offset of the next Token within the document.
+    pub fn offset(&self) -> usize {
+        self.position
+    }
+
+    /// Goes back to the Token starting at the offset, read already but left unused.
+    ///
+    /// The offset can't come before the last location asked for.
+    pub(crate) fn rewind(&mut self, offset: usize) {
+        debug_assert!(offset >= self.counted.offset);
+        self.position = offset;
+        self.raw_text = None;
+    }
+
+    /// Location of the next Token within the document.
+    pub fn location(&mut self) -> Location {
+        let passed = &self.html.as_bytes()[self.counted.offset..self.position];
+        // columns count characters, that is every byte but UTF-8 continuation ones
+        let characters = |bytes: &[u8]| bytes.iter().filter(|byte| (**byte as i8) >= -0x40).count();
+        match passed.iter().rposition(|byte| *byte == b'\n') {
+            Some(last) => {
+                self.counted.line += passed.iter().filter(|byte| **byte == b'\n').count();
+                self.counted.column = 1 + characters(&passed[last + 1..]);
+            }
+            None => self.counted.column += characters(passed),
+        }
+        self.counted.offset = self.position;
+
+        self.counted
+    }
+
+    /// Moves past a run of bytes which don't change the state, all at once.
+    fn skip(&mut self, plain: impl Fn(u8) -> bool) {
+        self.position += self.html.as_bytes()[self.position..]
+            .iter()
+            .take_while(|byte| plain(**byte))
+            .count();
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.html[self.position..]
+    }
+
+    /// Reads text up to the next `<` actually starting some markup.
+    fn text(&mut self) -> Token<'a> {
+        let rest = self.rest();
+        let bytes = rest.as_bytes();
+        let mut end = 1;
+        loop {
+            match bytes.get(end..).and_then(|tail| tail.iter().position(|byte| *byte == b'<')) {
+                Some(index) if starts_markup(&bytes[end + index + 1..]) => {
+                    end += index;
+                    break;
+                }
+                Some(index) => end += index + 1,
+                None => {
+                    end = bytes.len();
+                    break;
+                }
+            }
+        }
+        self.position += end;
+
+        Token::Text(entity::decode(&rest[..end]))
+    }
+
+    /// Reads the content of a raw text element, up to its own end tag.
+    fn raw_text(&mut self, name: &str, kind: TextKind) -> Option<Token<'a>> {
+        let rest = self.rest();
+        let bytes = rest.as_bytes();
+        let end = (0..bytes.len())
+            .find(|index| {
+                bytes[*index..].starts_with(b"</")
+                    && bytes[index + 2..]
+                        .get(..name.len())
+                        .is_some_and(|candidate| candidate.eq_ignore_ascii_case(name.as_bytes()))
+                    && bytes
+                        .get(index + 2 + name.len())
+                        .is_none_or(|next| next.is_ascii_whitespace() || *next == b'/' || *next == b'>')
+            })
+            .unwrap_or(bytes.len());
+        self.position += end;
+
+        match (end, kind) {
+            (0, _) => None,
+            (_, TextKind::Escapable) => Some(Token::Text(entity::decode(&rest[..end]))),
+            (_, TextKind::Raw) => Some(Token::Text(Cow::Borrowed(&rest[..end]))),
+        }
+    }
+
+    /// Reads a comment, a doctype, or anything else starting with `<!` or `<?`.
+    fn markup_declaration(&mut self) -> Token<'a> {
+        let rest = self.rest();
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            // `<!-->` and `<!--->` are complete, empty comments
+            let (content, length) = if comment.starts_with('>') {
+                ("", 5)
+            } else if comment.starts_with("->") {
+                ("", 6)
+            } else if let Some(end) = comment.find("-->") {
+                (&comment[..end], end + 7)
+            } else {
+                (comment, rest.len())
+            };
+            self.position += length;
+
+            return Token::Comment(content);
+        }
+        let (content, length) = match rest.find('>') {
+            Some(end) => (&rest[2..end], end + 1),
+            None => (&rest[2..], rest.len()),
+        };
+        self.position += length;
+        if content.len() >= 7 && content[..7].eq_ignore_ascii_case("doctype") {
+            Token::Doctype(content[7..].trim())
+        } else {
+            Token::Comment(content)
+        }
+    }
+
+    /// Runs the tag state machine over a start or end tag, the `<` being already consumed.
+    ///
+    /// Returns None if the document ends before the tag does.
+    fn tag(&mut self, kind: TagKind) -> Option<Token<'a>> {
+        let html = self.html;
+        let bytes = html.as_bytes();
+        let mut state = State::TagName;
+        let name_start = self.position;
+        let mut name = Cow::Borrowed("");
+        let mut attributes = Vec::new();
+        let mut attribute_name = Cow::Borrowed("");
+        let mut value_start = 0;
+        let mut self_closing = false;
+
+        loop {
+            let &byte = bytes.get(self.position)?;
+            self.position += 1;
+            match (state, byte) {
+                (State::TagName, b'\t' | b'\n' | b'\x0c' | b' ' | b'/' | b'>') => {
+                    name = lowercase(&html[name_start..self.position - 1]);
+                    self.position -= 1;
+                    state = State::BeforeAttributeName;
+                }
+                (State::TagName, _) => self.skip(|byte| !matches!(byte, b'\t' | b'\n' | b'\x0c' | b' ' | b'/' | b'>')),
+
+                (State::BeforeAttributeName, b'\t' | b'\n' | b'\x0c' | b' ') => (),
+                (State::BeforeAttributeName, b'/') => state = State::SelfClosingStartTag,
+                (State::BeforeAttributeName, b'>') => break,
+                (State::BeforeAttributeName, _) => {
+                    self.position -= 1;
+                    value_start = self.position;
+                    state = State::AttributeName;
+                }
+
+                (State::AttributeName, b'\t' | b'\n' | b'\x0c' | b' ' | b'/' | b'>' | b'=') => {
+                    attribute_name = lowercase(&html[value_start..self.position - 1]);
+                    self.position -= 1;
+                    state = State::AfterAttributeName;
+                }
+                (State::AttributeName, _) => {
+                    self.skip(|byte| !matches!(byte, b'\t' | b'\n' | b'\x0c' | b' ' | b'/' | b'>' | b'='))
+                }
+
+                (State::AfterAttributeName, b'\t' | b'\n' | b'\x0c' | b' ') => (),
+                (State::AfterAttributeName, b'=') => state = State::BeforeAttributeValue,
+                (State::AfterAttributeName, _) => {
+                    // a boolean attribute, such as `open`
+                    add_attribute(&mut attributes, std::mem::take(&mut attribute_name), Cow::Borrowed(""));
+                    self.position -= 1;
+                    state = State::BeforeAttributeName;
+                }
+
+                (State::BeforeAttributeValue, b'\t' | b'\n' | b'\x0c' | b' ') => (),
+                (State::BeforeAttributeValue, b'"' | b'\'') => {
+                    value_start = self.position;
+                    state = State::AttributeValueQuoted(byte);
+                }
+                (State::BeforeAttributeValue, _) => {
+                    self.position -= 1;
+                    value_start = self.position;
+                    state = State::AttributeValueUnquoted;
+                }
+
+                (State::AttributeValueQuoted(quote), _) if byte == quote => {
+                    let value = entity::decode_attribute(&html[value_start..self.position - 1]);
+                    add_attribute(&mut attributes, std::mem::take(&mut attribute_name), value);
+                    state = State::AfterAttributeValueQuoted;
+                }
+                (State::AttributeValueQuoted(quote), _) => self.skip(|byte| byte != quote),
+
+                (State::AttributeValueUnquoted, b'\t' | b'\n' | b'\x0c' | b' ' | b'>') => {
+                    let value = entity::decode_attribute(&html[value_start..self.position - 1]);
+                    add_attribute(&mut attributes, std::mem::take(&mut attribute_name), value);
+                    self.position -= 1;
+                    state = State::BeforeAttributeName;
+                }
+                (State::AttributeValueUnquoted, _) => self.skip(|byte| !matches!(byte, b'\t' | b'\n' | b'\x0c' | b' ' | b'>')),
+
+                (State::AfterAttributeValueQuoted | State::SelfClosingStartTag, b'>') => {
+                    self_closing = state == State::SelfClosingStartTag;
+                    break;
+                }
+                (State::AfterAttributeValueQuoted | State::SelfClosingStartTag, _) => {
+                    self.position -= 1;
+                    state = State::BeforeAttributeName;
+                }
+            }
+        }
+        if matches!(kind, TagKind::Closing) {
+            return Some(Token::EndTag(Tag {
+                name,
+                kind,
+                attributes: Vec::new(),
+            }));
+        }
+        if is_raw_text(&name) {
+            self.raw_text = Some((name.clone(), TextKind::Raw));
+        } else if name == "textarea" || name == "title" {
+            self.raw_text = Some((name.clone(), TextKind::Escapable));
+        }
+        let kind = if is_void(&name) { TagKind::Void } else { TagKind::Opening };
+
+        Some(Token::StartTag {
+            tag: Tag {
+                name,
+                kind,
+                attributes,
+            },
+            self_closing,
+        })
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        if let Some((name, kind)) = self.raw_text.take()
+            && let Some(text) = self.raw_text(&name, kind)
+        {
+            return Some(text);
+        }
+        loop {
+            let bytes = self.rest().as_bytes();
+            match bytes {
+                [] => return None,
+                [b'<', b'!' | b'?', ..] => return Some(self.markup_declaration()),
+                [b'<', b'/', b'>', ..] => self.position += 3,
+                [b'<', b'/', next, ..] if next.is_ascii_alphabetic() => {
+                    self.position += 2;
+                    return self.tag(TagKind::Closing);
+                }
+                [b'<', b'/', ..] => return Some(self.markup_declaration()),
+                [b'<', next, ..] if next.is_ascii_alphabetic() => {
+                    self.position += 1;
+                    return self.tag(TagKind::Opening);
+                }
+                _ => return Some(self.text()),
+            }
+        }
+    }
+}
+
+/// Adds an attribute to a tag, unless it's got one by that name already.
+fn add_attribute<'a>(attributes: &mut Attributes<'a>, name: Cow<'a, str>, value: Cow<'a, str>) {
+    if attributes.iter().all(|(existing, _)| *existing != name) {
+        attributes.push((name, value));
+    }
+}
+
+/// Lowercases a tag or attribute name, only allocating if it isn't lowercase already.
+fn lowercase(name: &str) -> Cow<'_, str> {
+    match name.bytes().any(|byte| byte.is_ascii_uppercase()) {
+        true => Cow::Owned(name.to_ascii_lowercase()),
+        false => Cow::Borrowed(name),
+    }
+}
+
+/// Whether the bytes following a `<` make it the start of a tag, a comment, or alike.
+fn starts_markup(bytes: &[u8]) -> bool {
+    match bytes {
+        [b'!' | b'?', ..] => true,
+        [b'/', next, ..] => next.is_ascii_alphabetic() || *next == b'>',
+        [next, ..] => next.is_ascii_alphabetic(),
+        [] => false,
+    }
+}
+
+/// Whether an element's content is left as is, character references and all.
+pub fn is_raw_text(name: &str) -> bool {
+    matches!(name, "script" | "style" | "xmp" | "iframe" | "noembed" | "noframes")
+}
+
+/// Whether an element never has any content nor end tag.
+pub fn is_void(name: &str) -> bool {
+    matches!(
+        name,
+        "area" | "base" | "br" | "col" | "embed" | "hr" | "img" | "input" | "link" | "meta"
+            | "param" | "source" | "track" | "wbr"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{Token, Tokenizer};
+    use crate::helper_types::tag::{Tag, TagKind};
+
+    fn start<'a>(name: &'a str, attributes: &[(&'a str, &'a str)]) -> Token<'a> {
+        Token::StartTag {
+            tag: Tag {
+                name: Cow::Borrowed(name),
+                kind: TagKind::Opening,
+                attributes: attributes
+                    .iter()
+                    .map(|(key, value)| (Cow::Borrowed(*key), Cow::Borrowed(*value)))
+                    .collect(),
+            },
+            self_closing: false,
+        }
+    }
+
+    fn end(name: &str) -> Token<'_> {
+        Token::EndTag(Tag {
+            name: Cow::Borrowed(name),
+            kind: TagKind::Closing,
+            attributes: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn tokenize_attributes() {
+        let html = r#"<details class="toggle" open><a title="Vec<T>" href='a.html' data-x=1>x &gt; y</a></details>"#;
+        assert_eq!(
+            Tokenizer::new(html).collect::<Vec<Token>>(),
+            Vec::from([
+                start("details", &[("class", "toggle"), ("open", "")]),
+                start("a", &[("title", "Vec<T>"), ("href", "a.html"), ("data-x", "1")]),
+                Token::Text(Cow::Borrowed("x > y")),
+                end("a"),
+                end("details"),
+            ])
+        );
+    }
+
+    #[test]
+    fn tokenize_comments_and_raw_text() {
+        let html = "<!DOCTYPE html><!-- <p> --><script>if (a<b) { x = '</p>'; }</script>a < b";
+        assert_eq!(
+            Tokenizer::new(html).collect::<Vec<Token>>(),
+            Vec::from([
+                Token::Doctype("html"),
+                Token::Comment(" <p> "),
+                start("script", &[]),
+                Token::Text(Cow::Borrowed("if (a<b) { x = '</p>'; }")),
+                end("script"),
+                Token::Text(Cow::Borrowed("a < b")),
+            ])
+        );
+    }
+}
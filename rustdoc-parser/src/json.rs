@@ -0,0 +1,1300 @@
+use std::{
+    borrow::Cow,
+    ops::RangeInclusive,
+};
+
+use serde_json::{
+    Map,
+    Value,
+};
+
+use crate::{
+    errors::Herr,
+    helper_types::{
+        description::Description,
+        fragment::Fragment,
+        method::Method
+        },
+    main_content::{
+        Field,
+        Implementation,
+        MainContent,
+        RequiredAssociatedType,
+        Section,
+        SectionContent,
+        TraitImplKind,
+        TraitImplementation,
+        Variant,
+        VariantKind,
+        Visibility,
+    },
+    markdown,
+    sidebar::{
+        ModuleNavigation,
+        Sidebar,
+        SidebarItem,
+        SidebarSection,
+    },
+    Page,
+};
+
+/////////////////////////////////////////////////////////////////////////////
+// JsonCrate
+/////////////////////////////////////////////////////////////////////////////
+
+/// The versions of rustdoc's JSON format a crate can be read in, older key names included.
+pub const FORMAT_VERSIONS: RangeInclusive<u64> = 33..=57;
+
+/// A whole crate, as documented by `rustdoc --output-format json`.
+///
+/// Each of its items can then be turned into the very Page its HTML documentation would give.
+#[derive(Debug)]
+pub struct JsonCrate {
+    pub format_version: u64,
+    index: Map<String, Value>,
+    paths: Map<String, Value>,
+}
+
+impl JsonCrate {
+    /// Reads the JSON output of rustdoc, provided it comes in one of the FORMAT_VERSIONS.
+    pub fn parse(json: &str) -> Result<Self, Herr> {
+        let mut krate: Value = serde_json::from_str(json).map_err(|err| Herr::Json(err.to_string()))?;
+        let format_version = krate["format_version"]
+            .as_u64()
+            .ok_or_else(|| Herr::Json(String::from("No `format_version` in the crate")))?;
+        if !FORMAT_VERSIONS.contains(&format_version) {
+            return Err(Herr::UnsupportedFormatVersion(format_version));
+        }
+        let mut take = |key: &'static str| match krate.get_mut(key).map(Value::take) {
+            Some(Value::Object(map)) => Ok(map),
+            _ => Err(Herr::Json(format!("No `{}` in the crate", key))),
+        };
+
+        Ok(JsonCrate {
+            index: take("index")?,
+            paths: take("paths")?,
+            format_version,
+        })
+    }
+
+    /// Lists the full path of each of the crate's own items, such as `shapes::Shape`.
+    pub fn items(&self) -> Vec<String> {
+        let mut items = self
+            .paths
+            .iter()
+            .filter(|(id, summary)| summary["crate_id"] == 0 && self.index.contains_key(*id))
+            .map(|(_, summary)| full_path(summary))
+            .collect::<Vec<String>>();
+        items.sort();
+
+        items
+    }
+
+    /// Builds the Page of the item at the given path, such as `shapes::Shape`.
+    pub fn page(&self, path: &str) -> Result<Page<'_>, Herr> {
+        let item = self
+            .paths
+            .iter()
+            .filter(|(_, summary)| full_path(summary) == path)
+            .find_map(|(id, _)| self.index.get(id))
+            .ok_or_else(|| Herr::UnknownItem(String::from(path)))?;
+        let (kind, inner) = inner(item)?;
+        let mut sections = Vec::new();
+        match kind {
+            "enum" => {
+                sections.push(section("Variants", self.variants(inner)?));
+                sections.extend(self.impl_sections(inner)?);
+            }
+            "struct" | "union" => {
+                if let Some(fields) = self.fields(kind, inner)? {
+                    sections.push(fields);
+                }
+                sections.extend(self.impl_sections(inner)?);
+            }
+            "trait" => sections.extend(self.trait_sections(inner)?),
+            _ => (),
+        }
+
+        Ok(Page {
+            version: None,
+            entry: Vec::from([Fragment::Raw(Cow::Owned(self.declaration(item)?))]),
+            sidebar: self.sidebar(path, &sections),
+            introduction: description(item),
+            main_content: MainContent(sections),
+            examples: Vec::new(),
+            source: None,
+        })
+    }
+
+    /////////////////////////////////////////////////////////////////////////
+    // Items
+    /////////////////////////////////////////////////////////////////////////
+
+    /// Looks an item up by its id, be it a number or a string as with older formats.
+    fn item(&self, id: &Value) -> Result<&Value, Herr> {
+        let key = match id {
+            Value::String(id) => id.clone(),
+            id => id.to_string(),
+        };
+        self.index
+            .get(&key)
+            .ok_or_else(|| Herr::Json(format!("No item {} in the index", key)))
+    }
+
+    /// Looks up every item out of a list of ids.
+    fn items_of<'a>(&'a self, ids: &'a Value) -> impl Iterator<Item = Result<&'a Value, Herr>> + 'a {
+        ids.as_array()
+            .into_iter()
+            .flatten()
+            .filter(|id| !id.is_null())
+            .map(|id| self.item(id))
+    }
+
+    /// Renders the item's declaration, as found on top of its page.
+    fn declaration(&self, item: &Value) -> Result<String, Herr> {
+        let (kind, inner) = inner(item)?;
+        let name = item["name"].as_str().unwrap_or_default();
+        let visibility = render_visibility(&item["visibility"]);
+        let generics = self.generics(&inner["generics"]);
+        let where_clause = self.where_clause(&inner["generics"]);
+        Ok(match kind {
+            "struct" | "union" => {
+                let declaration = format!("{}{} {}{}", visibility, kind, name, generics);
+                match &inner["kind"] {
+                    Value::String(unit) if unit == "unit" => format!("{}{};", declaration, where_clause),
+                    kind if kind.get("tuple").is_some() => {
+                        let fields = kind["tuple"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .map(|id| match id.is_null() {
+                                true => Ok(String::from("/* private field */")),
+                                false => {
+                                    let field = self.item(id)?;
+                                    Ok(format!("{}{}", render_visibility(&field["visibility"]), self.field_type(field)?))
+                                }
+                            })
+                            .collect::<Result<Vec<String>, Herr>>()?;
+                        format!("{}({}){};", declaration, fields.join(", "), where_clause)
+                    }
+                    kind => {
+                        let plain = kind.get("plain").unwrap_or(kind);
+                        let mut body = String::new();
+                        for field in self.items_of(&plain["fields"]) {
+                            let field = field?;
+                            body.push_str(&format!(
+                                "\n    {}{}: {},",
+                                render_visibility(&field["visibility"]),
+                                field["name"].as_str().unwrap_or_default(),
+                                self.field_type(field)?
+                            ));
+                        }
+                        if plain["has_stripped_fields"] == true || plain["fields_stripped"] == true {
+                            body.push_str("\n    /* private fields */");
+                        }
+                        format!("{}{} {{{}\n}}", declaration, where_clause, body)
+                    }
+                }
+            }
+            "enum" => {
+                let mut body = String::new();
+                for variant in self.items_of(&inner["variants"]) {
+                    body.push_str(&format!("\n    {},", self.variant(variant?)?));
+                }
+                if inner["has_stripped_variants"] == true || inner["variants_stripped"] == true {
+                    body.push_str("\n    // some variants omitted");
+                }
+                format!("{}enum {}{}{} {{{}\n}}", visibility, name, generics, where_clause, body)
+            }
+            "trait" => {
+                let mut body = String::new();
+                for member in self.items_of(&inner["items"]) {
+                    let member = member?;
+                    let provided = matches!(inner_kind(member), Some("function")) && member["inner"]["function"]["has_body"] == true;
+                    body.push_str(&format!(
+                        "\n    {}{}",
+                        self.declaration(member)?,
+                        if provided { " { ... }" } else { ";" }
+                    ));
+                }
+                let bounds = self.bounds(&inner["bounds"]);
+                format!(
+                    "{}{}{}trait {}{}{}{} {{{}\n}}",
+                    visibility,
+                    if inner["is_unsafe"] == true { "unsafe " } else { "" },
+                    if inner["is_auto"] == true { "auto " } else { "" },
+                    name,
+                    generics,
+                    if bounds.is_empty() { String::new() } else { format!(": {}", bounds) },
+                    where_clause,
+                    body
+                )
+            }
+            "function" => self.function(name, &visibility, inner),
+            "assoc_type" => {
+                let bounds = self.bounds(&inner["bounds"]);
+                let mut declaration = format!("type {}{}", name, generics);
+                if !bounds.is_empty() {
+                    declaration.push_str(&format!(": {}", bounds));
+                }
+                if let Some(default) = inner.get("type").or(inner.get("default")).filter(|ty| !ty.is_null()) {
+                    declaration.push_str(&format!(" = {}", self.render_type(default)));
+                }
+                declaration
+            }
+            "assoc_const" => {
+                let mut declaration = format!("const {}: {}", name, self.render_type(&inner["type"]));
+                if let Some(value) = inner.get("value").or(inner.get("default")).and_then(Value::as_str) {
+                    declaration.push_str(&format!(" = {}", value));
+                }
+                declaration
+            }
+            "type_alias" | "typedef" => format!(
+                "{}type {}{}{} = {};",
+                visibility,
+                name,
+                generics,
+                where_clause,
+                self.render_type(&inner["type"])
+            ),
+            "constant" => format!(
+                "{}const {}: {} = {};",
+                visibility,
+                name,
+                self.render_type(&inner["type"]),
+                inner["const"]["expr"].as_str().unwrap_or("_")
+            ),
+            "static" => format!(
+                "{}static {}{}: {};",
+                visibility,
+                if inner["is_mutable"] == true || inner["mutable"] == true { "mut " } else { "" },
+                name,
+                self.render_type(&inner["type"])
+            ),
+            "macro" => String::from(inner.as_str().unwrap_or(name)),
+            "module" => format!("{}mod {}", visibility, name),
+            kind => format!("{}{} {}", visibility, kind, name),
+        })
+    }
+
+    /// Renders a variant, along with its fields.
+    fn variant(&self, variant: &Value) -> Result<String, Herr> {
+        let (_, inner) = inner(variant)?;
+        let name = variant["name"].as_str().unwrap_or_default();
+        let kind = &inner["kind"];
+        let mut rendered = if let Some(fields) = kind.get("tuple") {
+            let types = self
+                .items_of(fields)
+                .map(|field| self.field_type(field?))
+                .collect::<Result<Vec<String>, Herr>>()?;
+            format!("{}({})", name, types.join(", "))
+        } else if let Some(fields) = kind.get("struct") {
+            let fields = self
+                .items_of(&fields["fields"])
+                .map(|field| {
+                    let field = field?;
+                    Ok(format!("{}: {}", field["name"].as_str().unwrap_or_default(), self.field_type(field)?))
+                })
+                .collect::<Result<Vec<String>, Herr>>()?;
+            format!("{} {{ {} }}", name, fields.join(", "))
+        } else {
+            String::from(name)
+        };
+        if let Some(discriminant) = inner["discriminant"]["expr"].as_str() {
+            rendered.push_str(&format!(" = {}", discriminant));
+        }
+
+        Ok(rendered)
+    }
+
+    /// The type of a field.
+    fn field_type(&self, field: &Value) -> Result<String, Herr> {
+        let (_, ty) = inner(field)?;
+        Ok(self.render_type(ty))
+    }
+
+    /// Renders a function's signature, be it a free function or a method.
+    fn function(&self, name: &str, visibility: &str, function: &Value) -> String {
+        let header = &function["header"];
+        let flag = |key: &str| header[format!("is_{}", key)] == true || header[key] == true;
+        let mut signature = String::from(visibility);
+        if flag("const") {
+            signature.push_str("const ");
+        }
+        if flag("async") {
+            signature.push_str("async ");
+        }
+        if flag("unsafe") {
+            signature.push_str("unsafe ");
+        }
+        signature.push_str(&abi(&header["abi"]));
+        let sig = function.get("sig").unwrap_or(&function["decl"]);
+        let inputs = sig["inputs"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|input| self.input(&input[0], &input[1]))
+            .collect::<Vec<String>>();
+        signature.push_str(&format!(
+            "fn {}{}({}{})",
+            name,
+            self.generics(&function["generics"]),
+            inputs.join(", "),
+            if sig["is_c_variadic"] == true || sig["c_variadic"] == true { ", ..." } else { "" }
+        ));
+        if !sig["output"].is_null() {
+            signature.push_str(&format!(" -> {}", self.render_type(&sig["output"])));
+        }
+        signature.push_str(&self.where_clause(&function["generics"]));
+
+        signature
+    }
+
+    /// Renders one of a function's inputs, taking care of the `self` shorthands.
+    fn input(&self, name: &Value, ty: &Value) -> String {
+        let name = name.as_str().unwrap_or("_");
+        if name == "self" {
+            if ty["generic"] == "Self" {
+                return String::from("self");
+            }
+            let reference = &ty["borrowed_ref"];
+            if reference["type"]["generic"] == "Self" {
+                return format!(
+                    "&{}{}self",
+                    reference["lifetime"].as_str().map(|lifetime| format!("{} ", lifetime)).unwrap_or_default(),
+                    if is_mutable(reference) { "mut " } else { "" }
+                );
+            }
+        }
+
+        format!("{}: {}", name, self.render_type(ty))
+    }
+
+    /////////////////////////////////////////////////////////////////////////
+    // Types
+    /////////////////////////////////////////////////////////////////////////
+
+    /// Renders a type the way it would be written down.
+    fn render_type(&self, ty: &Value) -> String {
+        if let Some(ty) = ty.as_str() {
+            return String::from(if ty == "infer" { "_" } else { ty });
+        }
+        let Some((kind, inner)) = single(ty) else {
+            return String::new();
+        };
+        match kind {
+            "resolved_path" => self.path(inner),
+            "generic" | "primitive" => String::from(inner.as_str().unwrap_or_default()),
+            "borrowed_ref" => format!(
+                "&{}{}{}",
+                inner["lifetime"].as_str().map(|lifetime| format!("{} ", lifetime)).unwrap_or_default(),
+                if is_mutable(inner) { "mut " } else { "" },
+                self.render_type(&inner["type"])
+            ),
+            "raw_pointer" => format!(
+                "*{} {}",
+                if is_mutable(inner) { "mut" } else { "const" },
+                self.render_type(&inner["type"])
+            ),
+            "slice" => format!("[{}]", self.render_type(inner)),
+            "array" => format!(
+                "[{}; {}]",
+                self.render_type(&inner["type"]),
+                inner["len"].as_str().unwrap_or("_")
+            ),
+            "pat" => self.render_type(&inner["type"]),
+            "tuple" => {
+                let types = inner
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|ty| self.render_type(ty))
+                    .collect::<Vec<String>>();
+                match types.len() {
+                    1 => format!("({},)", types[0]),
+                    _ => format!("({})", types.join(", ")),
+                }
+            }
+            "impl_trait" => format!("impl {}", self.bounds(inner)),
+            "dyn_trait" => {
+                let mut bounds = inner["traits"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|poly_trait| format!("{}{}", self.higher_ranked(&poly_trait["generic_params"]), self.path(&poly_trait["trait"])))
+                    .collect::<Vec<String>>();
+                if let Some(lifetime) = inner["lifetime"].as_str() {
+                    bounds.push(String::from(lifetime));
+                }
+                format!("dyn {}", bounds.join(" + "))
+            }
+            "qualified_path" => {
+                let self_type = self.render_type(&inner["self_type"]);
+                let name = inner["name"].as_str().unwrap_or_default();
+                // rustdoc leaves the trait out for generic types, as well as when the trait is unknown
+                match &inner["trait"] {
+                    qualifier if inner["self_type"].get("generic").is_none() && !self.path(qualifier).is_empty() => {
+                        format!("<{} as {}>::{}", self_type, self.path(qualifier), name)
+                    }
+                    _ => format!("{}::{}", self_type, name),
+                }
+            }
+            "function_pointer" => {
+                let sig = inner.get("sig").unwrap_or(&inner["decl"]);
+                let inputs = sig["inputs"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|input| self.render_type(&input[1]))
+                    .collect::<Vec<String>>();
+                let mut pointer = format!(
+                    "{}{}fn({})",
+                    self.higher_ranked(&inner["generic_params"]),
+                    abi(&inner["header"]["abi"]),
+                    inputs.join(", ")
+                );
+                if !sig["output"].is_null() {
+                    pointer.push_str(&format!(" -> {}", self.render_type(&sig["output"])));
+                }
+                pointer
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// Renders a path to a type or a trait, along with its generic arguments.
+    ///
+    /// Only the last segment is kept, as rustdoc does.
+    fn path(&self, path: &Value) -> String {
+        let name = path.get("path").or(path.get("name")).and_then(Value::as_str).unwrap_or_default();
+        let name = name.rsplit("::").next().unwrap_or_default();
+        format!("{}{}", name, self.generic_args(&path["args"]))
+    }
+
+    /// Renders the generic arguments of a path, be they angle bracketed or parenthesized.
+    fn generic_args(&self, args: &Value) -> String {
+        let Some((kind, inner)) = single(args) else {
+            return String::new();
+        };
+        match kind {
+            "angle_bracketed" => {
+                let mut rendered = inner["args"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|arg| match single(arg) {
+                        Some(("type", ty)) => self.render_type(ty),
+                        Some(("lifetime", lifetime)) => String::from(lifetime.as_str().unwrap_or_default()),
+                        Some(("const", constant)) => String::from(constant["expr"].as_str().unwrap_or("_")),
+                        _ => String::from("_"),
+                    })
+                    .collect::<Vec<String>>();
+                for constraint in inner
+                    .get("constraints")
+                    .or(inner.get("bindings"))
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    let name = format!(
+                        "{}{}",
+                        constraint["name"].as_str().unwrap_or_default(),
+                        self.generic_args(&constraint["args"])
+                    );
+                    rendered.push(match single(&constraint["binding"]) {
+                        Some(("equality", term)) => format!("{} = {}", name, self.term(term)),
+                        Some(("constraint", bounds)) => format!("{}: {}", name, self.bounds(bounds)),
+                        _ => name,
+                    });
+                }
+                match rendered.is_empty() {
+                    true => String::new(),
+                    false => format!("<{}>", rendered.join(", ")),
+                }
+            }
+            "parenthesized" => {
+                let inputs = inner["inputs"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|ty| self.render_type(ty))
+                    .collect::<Vec<String>>();
+                match inner["output"].is_null() {
+                    true => format!("({})", inputs.join(", ")),
+                    false => format!("({}) -> {}", inputs.join(", "), self.render_type(&inner["output"])),
+                }
+            }
+            _ => String::from("(..)"),
+        }
+    }
+
+    /// Renders the right-hand side of an equality, be it a type or a constant.
+    fn term(&self, term: &Value) -> String {
+        match single(term) {
+            Some(("type", ty)) => self.render_type(ty),
+            Some(("constant", constant)) => String::from(constant["expr"].as_str().unwrap_or("_")),
+            _ => self.render_type(term),
+        }
+    }
+
+    /// Renders a list of bounds, such as `Clone + 'static`.
+    fn bounds(&self, bounds: &Value) -> String {
+        bounds
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|bound| match single(bound) {
+                Some(("trait_bound", bound)) => format!(
+                    "{}{}{}",
+                    self.higher_ranked(&bound["generic_params"]),
+                    match bound["modifier"].as_str() {
+                        Some("maybe") => "?",
+                        Some("maybe_const") => "~const ",
+                        _ => "",
+                    },
+                    self.path(&bound["trait"])
+                ),
+                Some(("outlives", lifetime)) => String::from(lifetime.as_str().unwrap_or_default()),
+                Some(("use", captures)) => format!(
+                    "use<{}>",
+                    captures
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|capture| capture.as_str().or(single(capture).and_then(|(_, name)| name.as_str())))
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                ),
+                _ => String::new(),
+            })
+            .collect::<Vec<String>>()
+            .join(" + ")
+    }
+
+    /// Renders the `for<'a>` of higher ranked bounds.
+    fn higher_ranked(&self, params: &Value) -> String {
+        let params = params
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|param| param["name"].as_str())
+            .collect::<Vec<&str>>();
+        match params.is_empty() {
+            true => String::new(),
+            false => format!("for<{}> ", params.join(", ")),
+        }
+    }
+
+    /// Renders the generic parameters of an item, leaving out those standing for `impl Trait` arguments.
+    fn generics(&self, generics: &Value) -> String {
+        let params = generics["params"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|param| {
+                let name = param["name"].as_str().unwrap_or_default();
+                match single(&param["kind"]) {
+                    Some(("lifetime", lifetime)) => {
+                        let outlives = lifetime["outlives"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(Value::as_str)
+                            .collect::<Vec<&str>>();
+                        Some(match outlives.is_empty() {
+                            true => String::from(name),
+                            false => format!("{}: {}", name, outlives.join(" + ")),
+                        })
+                    }
+                    Some(("type", ty)) if ty["is_synthetic"] == true || ty["synthetic"] == true => None,
+                    Some(("type", ty)) => {
+                        let bounds = self.bounds(&ty["bounds"]);
+                        let mut param = String::from(name);
+                        if !bounds.is_empty() {
+                            param.push_str(&format!(": {}", bounds));
+                        }
+                        if !ty["default"].is_null() {
+                            param.push_str(&format!(" = {}", self.render_type(&ty["default"])));
+                        }
+                        Some(param)
+                    }
+                    Some(("const", constant)) => Some(format!("const {}: {}", name, self.render_type(&constant["type"]))),
+                    _ => Some(String::from(name)),
+                }
+            })
+            .collect::<Vec<String>>();
+        match params.is_empty() {
+            true => String::new(),
+            false => format!("<{}>", params.join(", ")),
+        }
+    }
+
+    /// Renders the where clause of an item, one predicate per line as rustdoc does.
+    fn where_clause(&self, generics: &Value) -> String {
+        let predicates = generics["where_predicates"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|predicate| match single(predicate)? {
+                ("bound_predicate", bound) => Some(format!(
+                    "{}{}: {}",
+                    self.higher_ranked(&bound["generic_params"]),
+                    self.render_type(&bound["type"]),
+                    self.bounds(&bound["bounds"])
+                )),
+                ("lifetime_predicate", lifetime) => Some(format!(
+                    "{}: {}",
+                    lifetime["lifetime"].as_str().unwrap_or_default(),
+                    lifetime["outlives"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<&str>>()
+                        .join(" + ")
+                )),
+                ("eq_predicate", equality) => Some(format!(
+                    "{} == {}",
+                    self.render_type(&equality["lhs"]),
+                    self.term(&equality["rhs"])
+                )),
+                _ => None,
+            })
+            .map(|predicate| format!("\n    {},", predicate))
+            .collect::<String>();
+        match predicates.is_empty() {
+            true => String::new(),
+            false => format!("\nwhere{}", predicates),
+        }
+    }
+
+    /////////////////////////////////////////////////////////////////////////
+    // Sections
+    /////////////////////////////////////////////////////////////////////////
+
+    /// Builds the Fields or Tuple Fields section of a struct or union, if it has any fields to show.
+    fn fields<'a>(&'a self, kind: &str, inner: &'a Value) -> Result<Option<Section<'a>>, Herr> {
+        let (name, ids) = match (&inner["kind"], kind) {
+            (kind, _) if kind.get("tuple").is_some() => ("Tuple Fields", &kind["tuple"]),
+            (kind, "struct") => ("Fields", &kind.get("plain").unwrap_or(kind)["fields"]),
+            (_, _) => ("Fields", &inner["fields"]),
+        };
+        let fields = self
+            .items_of(ids)
+            .map(|field| {
+                let field = field?;
+                self.field(anchor("structfield", field), field)
+            })
+            .collect::<Result<Vec<Field>, Herr>>()?;
+
+        Ok((!fields.is_empty()).then(|| section(name, SectionContent::Fields(fields))))
+    }
+
+    /// Builds one of the fields of a struct, a union or a variant.
+    fn field<'a>(&'a self, id: Option<Cow<'a, str>>, field: &'a Value) -> Result<Field<'a>, Herr> {
+        let deprecation = &field["deprecation"];
+        Ok(Field {
+            id,
+            name: String::from(field["name"].as_str().unwrap_or_default()),
+            r#type: Vec::from([Fragment::Raw(Cow::Owned(self.field_type(field)?))]),
+            visibility: match render_visibility(&field["visibility"]).trim() {
+                "pub" => Visibility::Public,
+                "" => Visibility::Private,
+                restricted => Visibility::Restricted(String::from(restricted)),
+            },
+            deprecation: deprecation.is_object().then(|| {
+                let since = deprecation["since"].as_str().map(|since| format!(" since {}", since));
+                let note = deprecation["note"].as_str().map(|note| format!(": {}", note));
+                Vec::from([Fragment::Raw(Cow::Owned(format!(
+                    "Deprecated{}{}",
+                    since.unwrap_or_default(),
+                    note.unwrap_or_default()
+                )))])
+            }),
+            description: description(field),
+        })
+    }
+
+    /// Builds the Variants section of an enum.
+    fn variants<'a>(&'a self, inner: &'a Value) -> Result<SectionContent<'a>, Herr> {
+        Ok(SectionContent::Variants(
+            self.items_of(&inner["variants"])
+                .map(|variant| {
+                    let variant = variant?;
+                    let (_, details) = self::inner(variant)?;
+                    let (kind, fields) = match &details["kind"] {
+                        kind if kind.get("tuple").is_some() => (VariantKind::Tuple, &kind["tuple"]),
+                        kind if kind.get("struct").is_some() => (VariantKind::Struct, &kind["struct"]["fields"]),
+                        _ => (VariantKind::Unit, &Value::Null),
+                    };
+                    let name = variant["name"].as_str().unwrap_or_default();
+                    let mut fields = self
+                        .items_of(fields)
+                        .map(|field| {
+                            let field = field?;
+                            let id = format!("variant.{}.field.{}", name, field["name"].as_str().unwrap_or_default());
+                            // a variant's fields are as visible as the enum itself
+                            let mut field = self.field(Some(Cow::Owned(id)), field)?;
+                            field.visibility = Visibility::Public;
+                            Ok(field)
+                        })
+                        .collect::<Result<Vec<Field>, Herr>>()?;
+                    // rustdoc only lists a tuple variant's fields if one of them is documented
+                    if kind == VariantKind::Tuple && fields.iter().all(|field| field.description.is_empty()) {
+                        fields.clear();
+                    }
+
+                    Ok(Variant {
+                        id: anchor("variant", variant),
+                        name: Fragment::Bold(Vec::from([Fragment::Raw(Cow::Owned(self.variant(variant)?))])),
+                        kind,
+                        fields,
+                        discriminant: details["discriminant"]["expr"].as_str().map(String::from),
+                        description: description(variant),
+                    })
+                })
+                .collect::<Result<Vec<Variant>, Herr>>()?,
+        ))
+    }
+
+    /// Sorts a type's impl blocks into the sections rustdoc shows them in.
+    fn impl_sections<'a>(&'a self, implementor: &'a Value) -> Result<Vec<Section<'a>>, Herr> {
+        let mut implementations = Vec::new();
+        let mut trait_implementations = Vec::new();
+        let mut auto_implementations = Vec::new();
+        let mut blanket_implementations = Vec::new();
+        for block in self.items_of(&implementor["impls"]) {
+            let (_, block) = inner(block?)?;
+            let header = Fragment::Bold(Vec::from([Fragment::Raw(Cow::Owned(self.impl_header(block)))]));
+            let methods = self.impl_methods(block)?;
+            match impl_kind(block) {
+                None => implementations.push(Implementation {
+                    id: None,
+                    inherent_impl: header,
+                    methods,
+                }),
+                Some(kind) => {
+                    let implementation = TraitImplementation::new(None, header, kind, methods);
+                    match kind {
+                        TraitImplKind::Trait => trait_implementations.push(implementation),
+                        TraitImplKind::Synthetic => auto_implementations.push(implementation),
+                        TraitImplKind::Blanket => blanket_implementations.push(implementation),
+                    }
+                }
+            }
+        }
+
+        Ok([
+            ("Implementations", SectionContent::Implementations(implementations)),
+            ("Trait Implementations", SectionContent::TraitImplementations(trait_implementations)),
+            ("Auto Trait Implementations", SectionContent::TraitImplementations(auto_implementations)),
+            ("Blanket Implementations", SectionContent::TraitImplementations(blanket_implementations)),
+        ]
+        .into_iter()
+        .filter(|(_, content)| match content {
+            SectionContent::Implementations(implementations) => !implementations.is_empty(),
+            SectionContent::TraitImplementations(implementations) => !implementations.is_empty(),
+            _ => true,
+        })
+        .map(|(name, content)| section(name, content))
+        .collect())
+    }
+
+    /// Renders an impl block's header, such as `impl<T> From<T> for T`.
+    fn impl_header(&self, block: &Value) -> String {
+        let mut header = format!(
+            "{}impl{} ",
+            if block["is_unsafe"] == true { "unsafe " } else { "" },
+            self.generics(&block["generics"])
+        );
+        if !block["trait"].is_null() {
+            header.push_str(&format!(
+                "{}{} for ",
+                if block["is_negative"] == true || block["negative"] == true { "!" } else { "" },
+                self.path(&block["trait"])
+            ));
+        }
+        // blanket impls are shown for their generic type rather than for the implementor
+        match block["blanket_impl"].is_null() {
+            true => header.push_str(&self.render_type(&block["for"])),
+            false => header.push_str(&self.render_type(&block["blanket_impl"])),
+        }
+        header.push_str(&self.where_clause(&block["generics"]));
+
+        header
+    }
+
+    /// Builds the methods of an impl block, along with its associated types and constants.
+    ///
+    /// Trait methods without docs of their own borrow those of the trait, as rustdoc does.
+    fn impl_methods<'a>(&'a self, block: &'a Value) -> Result<Vec<Method<'a>>, Herr> {
+        let trait_items = block["trait"]
+            .get("id")
+            .and_then(|id| self.item(id).ok())
+            .and_then(|item| item["inner"]["trait"]["items"].as_array());
+        self.items_of(&block["items"])
+            .map(|member| {
+                let member = member?;
+                let documented = match member["docs"].is_string() {
+                    true => member,
+                    false => trait_items
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|id| self.item(id).ok())
+                        .find(|provided| provided["name"] == member["name"])
+                        .unwrap_or(member),
+                };
+                Ok(Method {
+                    id: match inner_kind(member) {
+                        Some("assoc_type") => anchor("associatedtype", member),
+                        Some("assoc_const") => anchor("associatedconstant", member),
+                        _ => anchor("method", member),
+                    },
+                    signature: Vec::from([Fragment::Raw(Cow::Owned(self.declaration(member)?))]),
+                    description: description(documented),
+                    notable_type: None,
+                    notable_traits: Vec::new(),
+                    examples: Vec::new(),
+                    source: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Builds the sections of a trait's page.
+    fn trait_sections<'a>(&'a self, inner: &'a Value) -> Result<Vec<Section<'a>>, Herr> {
+        let mut associated_types = Vec::new();
+        let mut required_methods = Vec::new();
+        let mut provided_methods = Vec::new();
+        for member in self.items_of(&inner["items"]) {
+            let member = member?;
+            match inner_kind(member) {
+                Some("assoc_type") => associated_types.push(RequiredAssociatedType {
+                    id: anchor("associatedtype", member),
+                    name: Fragment::Bold(Vec::from([Fragment::Raw(Cow::Owned(self.declaration(member)?))])),
+                    description: Fragment::Raw(Cow::Owned(
+                        first_paragraph(member)
+                            .iter()
+                            .filter_map(|fragment| fragment.raw_content())
+                            .collect(),
+                    )),
+                }),
+                Some("function") if member["inner"]["function"]["has_body"] == true => {
+                    provided_methods.push(self.trait_method("method", member)?)
+                }
+                Some("function") => required_methods.push(self.trait_method("tymethod", member)?),
+                _ => (),
+            }
+        }
+        let mut sections = Vec::new();
+        if !associated_types.is_empty() {
+            sections.push(section(
+                "Required Associated Types",
+                SectionContent::RequiredAssociatedTypes(associated_types),
+            ));
+        }
+        if !required_methods.is_empty() {
+            sections.push(section("Required Methods", SectionContent::Methods(required_methods)));
+        }
+        if !provided_methods.is_empty() {
+            sections.push(section("Provided Methods", SectionContent::Methods(provided_methods)));
+        }
+        if inner.get("is_dyn_compatible").or(inner.get("is_object_safe")) == Some(&Value::Bool(false)) {
+            sections.push(section(
+                "Dyn Compatibility",
+                SectionContent::ObjectSafety(Vec::from([
+                    Fragment::Raw(Cow::Borrowed("This trait is ")),
+                    Fragment::Bold(Vec::from([Fragment::Raw(Cow::Borrowed("not"))])),
+                    Fragment::Raw(Cow::Borrowed(" dyn compatible.")),
+                ])),
+            ));
+        }
+        let implementors = self
+            .items_of(&inner["implementations"])
+            .map(|block| {
+                let block = &block?["inner"]["impl"];
+                let header = Fragment::Bold(Vec::from([Fragment::Raw(Cow::Owned(self.impl_header(block)))]));
+                Ok(TraitImplementation::new(None, header, TraitImplKind::Trait, self.impl_methods(block)?))
+            })
+            .collect::<Result<Vec<TraitImplementation>, Herr>>()?;
+        if !implementors.is_empty() {
+            sections.push(section("Implementors", SectionContent::TraitImplementations(implementors)));
+        }
+
+        Ok(sections)
+    }
+
+    /// Builds one of a trait's methods, its id starting with `tymethod` if required or `method` if provided.
+    fn trait_method<'a>(&'a self, kind: &str, member: &'a Value) -> Result<Method<'a>, Herr> {
+        Ok(Method {
+            id: anchor(kind, member),
+            signature: Vec::from([Fragment::Raw(Cow::Owned(self.declaration(member)?))]),
+            description: description(member),
+            notable_type: None,
+            notable_traits: Vec::new(),
+            examples: Vec::new(),
+            source: None,
+        })
+    }
+
+    /// Lists, for each section, the names the sidebar would link to, along with the item's module.
+    ///
+    /// Impl blocks get no anchor, rustdoc's ids for those being out of reach.
+    fn sidebar(&self, path: &str, sections: &[Section]) -> Sidebar {
+        let names = |fragments: &[Fragment]| {
+            fragments
+                .iter()
+                .filter_map(|fragment| fragment.raw_content())
+                .collect::<String>()
+        };
+        // links to the item's id, its kind being the one the id starts with unless told otherwise
+        let item = |name: String, id: &Option<Cow<str>>, kind: Option<&str>| {
+            let target = id.as_ref().map(|id| format!("#{}", id));
+            SidebarItem {
+                kind: kind
+                    .or_else(|| target.as_deref().and_then(SidebarItem::kind))
+                    .map(String::from),
+                target,
+                name,
+            }
+        };
+        let sections = sections
+            .iter()
+            .filter_map(|section| {
+                let (name, items) = match &section.content {
+                    SectionContent::Fields(fields) => (
+                        "Fields",
+                        fields
+                            .iter()
+                            .map(|field| item(field.name.clone(), &field.id, None))
+                            .collect(),
+                    ),
+                    SectionContent::Variants(variants) => (
+                        "Variants",
+                        variants
+                            .iter()
+                            .map(|variant| {
+                                let name = bold_text(&variant.name);
+                                let name = String::from(name.split(['(', ' ', '{']).next().unwrap_or_default());
+                                item(name, &variant.id, None)
+                            })
+                            .collect(),
+                    ),
+                    SectionContent::Implementations(implementations) => (
+                        "Methods",
+                        implementations
+                            .iter()
+                            .flat_map(|implementation| implementation.methods.iter())
+                            .filter_map(|method| Some(item(method_name(&names(&method.signature))?, &method.id, None)))
+                            .collect(),
+                    ),
+                    SectionContent::Methods(methods) => (
+                        section_name(section),
+                        methods
+                            .iter()
+                            .filter_map(|method| Some(item(method_name(&names(&method.signature))?, &method.id, None)))
+                            .collect(),
+                    ),
+                    // the sidebar leaving implementors out, as rustdoc's does
+                    SectionContent::TraitImplementations(_) if section_name(section) == "Implementors" => ("Implementors", Vec::new()),
+                    SectionContent::TraitImplementations(implementations) => {
+                        let kind = match section_name(section) {
+                            "Auto Trait Implementations" => "synthetic-implementation",
+                            "Blanket Implementations" => "blanket-implementation",
+                            _ => "trait-implementation",
+                        };
+                        (
+                            section_name(section),
+                            implementations
+                                .iter()
+                                .map(|implementation| {
+                                    let name = implemented_trait(&bold_text(&implementation.trait_impl));
+                                    item(name, &implementation.id, Some(kind))
+                                })
+                                .collect(),
+                        )
+                    }
+                    SectionContent::RequiredAssociatedTypes(types) => (
+                        "Required Associated Types",
+                        types
+                            .iter()
+                            .filter_map(|ty| Some(item(method_name(&bold_text(&ty.name))?, &ty.id, None)))
+                            .collect(),
+                    ),
+                    _ => return None,
+                };
+                let target = section.id.as_ref().map(|id| format!("#{}", id));
+                Some(SidebarSection::new(String::from(name), target, items))
+            })
+            .collect();
+
+        Sidebar {
+            sections,
+            module: path.rsplit_once("::").map(|(module, _)| ModuleNavigation {
+                path: String::from(module),
+                target: Some(String::from("index.html")),
+                sections: Vec::new(),
+            }),
+        }
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Helpers
+/////////////////////////////////////////////////////////////////////////////
+
+/// Joins an item summary's path, such as `["shapes", "Shape"]`, into `shapes::Shape`.
+fn full_path(summary: &Value) -> String {
+    summary["path"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect::<Vec<&str>>()
+        .join("::")
+}
+
+/// Splits an externally tagged value, such as `{"struct": {...}}`, into its tag and content.
+fn single(value: &Value) -> Option<(&str, &Value)> {
+    let map = value.as_object()?;
+    match map.len() {
+        1 => map.iter().next().map(|(kind, inner)| (kind.as_str(), inner)),
+        _ => None,
+    }
+}
+
+/// The kind of an item along with its details, be it `{"inner": {"struct": ..}}` or the `kind` and `inner` of older formats.
+fn inner(item: &Value) -> Result<(&str, &Value), Herr> {
+    match item["kind"].as_str() {
+        Some(kind) => Some((kind, &item["inner"])),
+        None => single(&item["inner"]),
+    }
+    .ok_or_else(|| Herr::Json(format!("The item {} has no kind", item["id"])))
+}
+
+/// The kind of an item, such as `struct`.
+fn inner_kind(item: &Value) -> Option<&str> {
+    inner(item).ok().map(|(kind, _)| kind)
+}
+
+/// Where an impl block belongs, inherent ones being no trait implementations at all.
+fn impl_kind(block: &Value) -> Option<TraitImplKind> {
+    if block["trait"].is_null() {
+        None
+    } else if block["is_synthetic"] == true || block["synthetic"] == true {
+        Some(TraitImplKind::Synthetic)
+    } else if !block["blanket_impl"].is_null() {
+        Some(TraitImplKind::Blanket)
+    } else {
+        Some(TraitImplKind::Trait)
+    }
+}
+
+/// Whether a reference or pointer is mutable, as told by either format.
+fn is_mutable(reference: &Value) -> bool {
+    reference["is_mutable"] == true || reference["mutable"] == true
+}
+
+/// Renders an item's visibility, such as `pub `.
+fn render_visibility(visibility: &Value) -> String {
+    match visibility {
+        Value::String(visibility) if visibility == "public" => String::from("pub "),
+        Value::String(visibility) if visibility == "crate" => String::from("pub(crate) "),
+        Value::Object(restricted) => format!(
+            "pub(in {}) ",
+            restricted
+                .get("restricted")
+                .and_then(|restricted| restricted["path"].as_str())
+                .unwrap_or_default()
+        ),
+        _ => String::new(),
+    }
+}
+
+/// Renders a function's ABI, such as `extern "C" `, Rust's own being left out.
+fn abi(abi: &Value) -> String {
+    match abi {
+        Value::String(abi) if abi == "Rust" => String::new(),
+        Value::String(abi) => format!("extern \"{}\" ", abi),
+        Value::Object(_) => match single(abi) {
+            Some(("Other", other)) => format!("extern {} ", other.as_str().unwrap_or_default()),
+            Some((abi, _)) => format!("extern \"{}\" ", abi),
+            None => String::new(),
+        },
+        _ => String::new(),
+    }
+}
+
+/// Builds a section out of its name and content.
+fn section<'a>(name: &'static str, content: SectionContent<'a>) -> Section<'a> {
+    Section {
+        id: section_id(name).map(Cow::Borrowed),
+        name: Fragment::Bold(Vec::from([Fragment::Raw(Cow::Borrowed(name))])),
+        content,
+    }
+}
+
+/// The id rustdoc gives a section's heading.
+fn section_id(name: &str) -> Option<&'static str> {
+    match name {
+        "Auto Trait Implementations" => Some("synthetic-implementations"),
+        "Blanket Implementations" => Some("blanket-implementations"),
+        "Dyn Compatibility" => Some("dyn-compatibility"),
+        "Fields" | "Tuple Fields" => Some("fields"),
+        "Implementations" => Some("implementations"),
+        "Implementors" => Some("implementors"),
+        "Provided Methods" => Some("provided-methods"),
+        "Required Associated Types" => Some("required-associated-types"),
+        "Required Methods" => Some("required-methods"),
+        "Trait Implementations" => Some("trait-implementations"),
+        "Variants" => Some("variants"),
+        _ => None,
+    }
+}
+
+/// The id rustdoc gives an item of the page, such as `method.map`, leaving out the `-1` it appends to repeated ones.
+fn anchor(kind: &str, item: &Value) -> Option<Cow<'static, str>> {
+    item["name"]
+        .as_str()
+        .map(|name| Cow::Owned(format!("{}.{}", kind, name)))
+}
+
+/// The name of a section.
+fn section_name<'r>(section: &'r Section) -> &'r str {
+    match &section.name {
+        Fragment::Bold(name) => name.first().and_then(|name| name.raw_content()).unwrap_or_default(),
+        _ => "",
+    }
+}
+
+/// The text of a bold fragment.
+fn bold_text(fragment: &Fragment) -> String {
+    match fragment {
+        Fragment::Bold(fragments) => fragments.iter().filter_map(|fragment| fragment.raw_content()).collect(),
+        fragment => String::from(fragment.raw_content().unwrap_or_default()),
+    }
+}
+
+/// The name of a function, or of an associated type or constant, out of its signature.
+fn method_name(signature: &str) -> Option<String> {
+    let mut words = signature.split(|character: char| !(character.is_alphanumeric() || character == '_'));
+    words
+        .find(|word| matches!(*word, "fn" | "type" | "const"))
+        .and_then(|_| words.find(|word| !word.is_empty()))
+        .map(String::from)
+}
+
+/// The trait an impl block implements, out of its header.
+fn implemented_trait(header: &str) -> String {
+    let header = header.split("\nwhere").next().unwrap_or_default();
+    let header = header.split_once(" for ").map(|(implemented, _)| implemented).unwrap_or(header);
+    let header = header.strip_prefix("unsafe ").unwrap_or(header);
+    let header = header.strip_prefix("impl").unwrap_or(header);
+    // skips the generic parameters of the impl block itself
+    let mut depth = 0;
+    let start = header
+        .char_indices()
+        .find(|(_, character)| {
+            match character {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                _ => return depth == 0 && !character.is_whitespace(),
+            }
+            false
+        })
+        .map(|(start, _)| start)
+        .unwrap_or_default();
+
+    String::from(header[start..].trim())
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Docs
+/////////////////////////////////////////////////////////////////////////////
+
+/// Whether the item's docs link to the given reference, as intra-doc links do.
+fn links(item: &Value) -> impl Fn(&str) -> bool + '_ {
+    |reference| item["links"].get(reference).is_some()
+}
+
+/// Parses an item's docs into a Description.
+fn description(item: &Value) -> Description<'_> {
+    item["docs"]
+        .as_str()
+        .map(|docs| markdown::parse_with_links(docs, links(item)))
+        .unwrap_or_default()
+}
+
+/// Parses the first paragraph of an item's docs.
+fn first_paragraph(item: &Value) -> Vec<Fragment<'_>> {
+    item["docs"]
+        .as_str()
+        .map(|docs| markdown::first_paragraph(docs, links(item)))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::JsonCrate;
+    use crate::{
+        errors::Herr,
+        helper_types::fragment::Fragment,
+        main_content::{
+            Anchored,
+            SectionContent,
+            VariantKind,
+        },
+    };
+
+    /// Generated out of `fixtures/shapes.rs` by `cargo +nightly rustdoc -- -Z unstable-options --output-format json`.
+    const SHAPES: &str = include_str!("../fixtures/shapes.json");
+
+    #[test]
+    fn json_pages() {
+        let krate = JsonCrate::parse(SHAPES).unwrap();
+        assert_eq!(krate.items(), ["shapes", "shapes::Measure", "shapes::Point", "shapes::Shape", "shapes::Shape::Circle", "shapes::Shape::Empty", "shapes::Shape::Square"]);
+
+        let shape = krate.page("shapes::Shape").unwrap();
+        assert_eq!(
+            shape.entry,
+            [Fragment::Raw(Cow::from("pub enum Shape {\n    Square(f64),\n    Circle { center: Point, radius: f64 },\n    Empty,\n}"))]
+        );
+        let sections = &shape.main_content.0;
+        let SectionContent::Variants(variants) = &sections[0].content else {
+            panic!("{:?}", sections[0]);
+        };
+        assert_eq!(variants[0].kind, VariantKind::Tuple);
+        assert_eq!(variants[1].fields.len(), 2);
+        assert_eq!(variants[0].description.introduction[1], Fragment::Bold(Vec::from([Fragment::Raw(Cow::from("side"))])));
+        let SectionContent::Implementations(implementations) = &sections[1].content else {
+            panic!("{:?}", sections[1]);
+        };
+        let scale = &implementations[0].methods[1];
+        assert_eq!(scale.signature, [Fragment::Raw(Cow::from("pub fn scale<T: Into<f64>>(&mut self, factor: T) -> &mut Self"))]);
+        let area = &implementations[0].methods[0];
+        assert_eq!(area.description.sections.len(), 1);
+        let SectionContent::TraitImplementations(blanket) = &sections.last().unwrap().content else {
+            panic!("{:?}", sections.last());
+        };
+        assert!(blanket.iter().any(|implementation| implementation.trait_impl == Fragment::Bold(Vec::from([Fragment::Raw(Cow::from("impl<T> Borrow<T> for T\nwhere\n    T: ?Sized,"))]))));
+
+        let measure = krate.page("shapes::Measure").unwrap();
+        assert!(matches!(measure.main_content.0[0].content, SectionContent::RequiredAssociatedTypes(_)));
+        let Some(Anchored::Method(required)) = measure.find_anchor("tymethod.measure") else {
+            panic!("{:?}", measure.main_content);
+        };
+        assert_eq!(required.signature, [Fragment::Raw(Cow::from("fn measure(&self) -> Self::Unit"))]);
+        assert!(matches!(measure.find_anchor("method.is_empty"), Some(Anchored::Method(_))));
+        let SectionContent::TraitImplementations(implementors) = &measure.main_content.0[3].content else {
+            panic!("{:?}", measure.main_content.0[3]);
+        };
+        assert_eq!(implementors[0].trait_impl, Fragment::Bold(Vec::from([Fragment::Raw(Cow::from("impl Measure for Shape"))])));
+        assert_eq!(implementors[0].methods.len(), 2);
+        assert!(krate.page("shapes::Triangle").is_err());
+
+        let future = SHAPES.replacen("\"format_version\":57", "\"format_version\":999", 1);
+        assert_eq!(JsonCrate::parse(&future).unwrap_err(), Herr::UnsupportedFormatVersion(999));
+    }
+}
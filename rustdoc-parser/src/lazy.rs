@@ -0,0 +1,242 @@
+use std::{
+    borrow::Cow,
+    cell::OnceCell,
+    collections::HashMap,
+    ops::Range,
+};
+
+use crate::{
+    check_body,
+    errors::Herr,
+    helper_types::{
+        description::Description,
+        fragment::Fragment,
+        html_element::HtmlElement,
+        method::NotableTrait,
+        scraped_example::ScrapedExample
+        },
+    main_content::{
+        MainContent,
+        Section
+    },
+    sidebar::Sidebar,
+    stream::{
+        Event,
+        PageStream,
+    },
+    version::{
+        Adapter,
+        RustdocVersion,
+    },
+    whole_body,
+    Page,
+    Preamble,
+};
+
+/////////////////////////////////////////////////////////////////////////////
+// LazyPage
+/////////////////////////////////////////////////////////////////////////////
+
+/// A page whose sections are only parsed once asked for, the first scan merely telling where they lie.
+///
+/// Everything but the main content's sections is parsed right away.
+#[derive(Debug)]
+pub struct LazyPage<'a> {
+    html: &'a str,
+    adapter: Adapter,
+    pub entry: Vec<Fragment<'a>>,
+    pub sidebar: Sidebar,
+    pub introduction: Description<'a>,
+    pub examples: Vec<ScrapedExample>,
+    pub source: Option<String>,
+    sections: Vec<LazySection<'a>>,
+    /// The notable traits' JSON, as the scan found it.
+    notable_data: Cow<'a, str>,
+    /// Only parsed once a section is.
+    notable_traits: OnceCell<HashMap<String, Vec<NotableTrait>>>,
+}
+
+/// One of the main content's sections, along with its parsing once done.
+#[derive(Debug)]
+struct LazySection<'a> {
+    name: String,
+    /// From the start of its heading to the start of the next one.
+    span: Range<usize>,
+    section: OnceCell<Result<Section<'a>, Herr>>,
+}
+
+impl<'a> LazyPage<'a> {
+    /// Goes through the HTML document once, recording where each section lies.
+    ///
+    /// Fails just as `process_html` would on a page which isn't an item's documentation, or is missing its main content.
+    pub fn scan(html: &'a str) -> Result<Self, Herr> {
+        let mut adapter = Adapter::default();
+        let mut body = None;
+        let mut sidebar = None;
+        let mut main = None;
+        let mut sections: Vec<LazySection> = Vec::new();
+        let mut notable_data = Cow::Borrowed("");
+        for event in PageStream::skimming(html) {
+            match event {
+                Event::Version(version) => adapter = Adapter::new(Some(version)),
+                Event::Body(element) => {
+                    check_body(html, &element)?;
+                    body = Some(element);
+                }
+                Event::Sidebar(nav) => sidebar = Some(nav),
+                Event::Preamble(element) => main = Some(element),
+                Event::Heading(heading) => {
+                    let start = heading.location().offset;
+                    if let Some(previous) = sections.last_mut() {
+                        previous.span.end = start;
+                    }
+                    sections.push(LazySection {
+                        name: Section::name(&heading),
+                        span: start..html.len(),
+                        section: OnceCell::new(),
+                    });
+                }
+                Event::Content(_) => (),
+                Event::NotableTraits(data) => notable_data = data,
+                Event::MainEnd(end) => {
+                    if let Some(last) = sections.last_mut() {
+                        last.span.end = end;
+                    }
+                }
+            }
+        }
+        if body.is_none() {
+            return Err(Herr::missing_section("<body>", &HtmlElement::parse_document(html)));
+        }
+        let main = main.ok_or_else(|| whole_body(html, |body| Herr::missing_section("#main-content", body)))?;
+        let preamble = Preamble::parse(&main, sidebar.as_ref(), &adapter);
+
+        Ok(LazyPage {
+            html,
+            adapter,
+            entry: preamble.entry,
+            sidebar: preamble.sidebar,
+            introduction: preamble.introduction,
+            examples: preamble.examples,
+            source: preamble.source,
+            sections,
+            notable_data,
+            notable_traits: OnceCell::new(),
+        })
+    }
+
+    /// The version of rustdoc which generated the page, if it says so.
+    pub fn version(&self) -> Option<RustdocVersion> {
+        self.adapter.version
+    }
+
+    /// Names of the main content's sections, in the order they come in.
+    pub fn section_names(&self) -> impl Iterator<Item = &str> + use<'_, 'a> {
+        self.sections.iter().map(|section| section.name.as_str())
+    }
+
+    /// Parses the section going by that name, unless it was already.
+    ///
+    /// "Object Safety" and "Dyn Compatibility" both name the very same section.
+    pub fn section(&self, name: &str) -> Option<Result<&Section<'a>, &Herr>> {
+        let name = self.adapter.section_name(name);
+        self.sections
+            .iter()
+            .find(|section| self.adapter.section_name(&section.name) == name)
+            .map(|section| self.parse(section))
+    }
+
+    /// Parses every section which wasn't already.
+    pub fn sections(&self) -> impl Iterator<Item = Result<&Section<'a>, &Herr>> {
+        self.sections.iter().map(|section| self.parse(section))
+    }
+
+    /// Turns into a whole Page, parsing every section which wasn't already and failing on the first broken one.
+    pub fn into_page(self) -> Result<Page<'a>, Herr> {
+        let adapter = self.adapter;
+        let html = self.html;
+        let entry = self.entry;
+        let notable_data = self.notable_data;
+        let notable_traits = self.notable_traits.into_inner().unwrap_or_else(|| NotableTrait::parse_data(&notable_data));
+        let sections = self
+            .sections
+            .into_iter()
+            .map(|section| {
+                let span = section.span;
+                section
+                    .section
+                    .into_inner()
+                    .unwrap_or_else(|| LazyPage::parse_span(html, span, &adapter, &entry, &notable_traits))
+            })
+            .collect::<Result<Vec<Section>, Herr>>()?;
+
+        Ok(Page {
+            version: adapter.version,
+            entry,
+            sidebar: self.sidebar,
+            introduction: self.introduction,
+            main_content: MainContent(sections),
+            examples: self.examples,
+            source: self.source,
+        })
+    }
+
+    fn parse<'r>(&self, section: &'r LazySection<'a>) -> Result<&'r Section<'a>, &'r Herr> {
+        section
+            .section
+            .get_or_init(|| {
+                let notable_traits = self.notable_traits.get_or_init(|| NotableTrait::parse_data(&self.notable_data));
+                LazyPage::parse_span(self.html, section.span.clone(), &self.adapter, &self.entry, notable_traits)
+            })
+            .as_ref()
+    }
+
+    /// Parses the section lying within the span, its heading coming first, the entry telling its fields' visibilities.
+    fn parse_span(
+        html: &'a str,
+        span: Range<usize>,
+        adapter: &Adapter,
+        entry: &[Fragment],
+        notable_traits: &HashMap<String, Vec<NotableTrait>>,
+    ) -> Result<Section<'a>, Herr> {
+        let elements = HtmlElement::parse_span(html, span);
+        let (heading, content) = elements
+            .split_first()
+            .ok_or(Herr::Parsing("A section went missing since the page was scanned"))?;
+
+        let mut section = Section::parse(heading, &content.iter().collect::<Vec<&HtmlElement>>(), adapter)?;
+        section.declare_visibilities(&entry.iter().map(Fragment::to_string).collect::<String>());
+        section.declare_notable_traits(notable_traits);
+
+        Ok(section)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LazyPage;
+    use crate::{
+        errors::Herr,
+        main_content::SectionContent,
+        process_html,
+    };
+
+    #[test]
+    fn lazy_sections() {
+        let page = "<body class=\"rustdoc enum\"><section id=\"main-content\">\
+            <h2 class=\"section-header\">Variants</h2><div class=\"variants\"><section class=\"variant\"><h3>A</h3></section></div>\
+            <h2 class=\"section-header\">Implementations</h2><div id=\"implementations-list\">\
+            <details class=\"toggle implementors-toggle\"><summary><section class=\"impl\"><h3 class=\"code-header\">impl E</h3></section></summary></details></div>\
+            </section></body>";
+        let lazy = LazyPage::scan(page).unwrap();
+        assert_eq!(lazy.section_names().collect::<Vec<&str>>(), ["Variants", "Implementations"]);
+        let implementations = lazy.section("Implementations").unwrap().unwrap();
+        assert!(matches!(implementations.content, SectionContent::Implementations(_)));
+        assert!(matches!(lazy.section("Variants"), Some(Err(Herr::UnexpectedTag { .. }))));
+        assert!(lazy.section("Fields").is_none());
+        assert_eq!(
+            lazy.into_page().unwrap_err(),
+            process_html(page).unwrap_err()
+        );
+    }
+}
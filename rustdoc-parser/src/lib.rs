@@ -4,9 +4,26 @@
 //!
 //! # Parsing
 //!
-//! Through extensive splitting and trimming, we make our way through the HTML source.
+//! The HTML source is tokenized and built up into a tree of HtmlElements, through which CSS selectors make our way.
 //!
-//! With the help of the Html type, the important elements are brought to a then agnostic life.
+//! Pages go through the Tokenizer in a single forward pass, a PageStream only building up the sidebar and the main content's elements.
+//! A LazyPage goes further, only recording where sections lie and parsing one once it's asked for.
+//!
+//! Whole documentation trees are read and parsed across all cores through a DocRoot.
+//!
+//! Once parsed, Pages are walked through with the Visit and VisitMut traits, overriding only the nodes of interest.
+//!
+//! Tokens, HtmlElements and Fragments all borrow from the source, text only being copied where character references get decoded.
+//!
+//! With the help of the HtmlElement type, the important elements are brought to a then agnostic life.
+//!
+//! Crates documented through `rustdoc --output-format json` make it to the very same Pages.
+//!
+//! Source pages, which items' `Source` links lead to, are parsed apart into SourcePages, one highlighted line after the other.
+//!
+//! Markdown docs, be they out of rustdoc JSON, doc comments or READMEs, are zipped into Fragments just as their HTML would be.
+//!
+//! The rustdoc version is read off the page's head, and an Adapter steers parsing through the markup of that very version.
 //!
 //! # Agnosticity
 //!
@@ -23,54 +40,251 @@
 //! Auto Trait Implementations, Immplementation and Variants are a-OK.
 //!
 //! On to Trait Implementations.
+//!
+//! # Serde
+//!
+//! With the `serde` feature, Pages and everything they hold derive `Serialize` and `Deserialize`,
+//! Parsed and Herr being only ever serialized. In JSON :
+//!
+//! - a Page is `{"version": {"major": 1, "minor": 95, "patch": 0} | null, "entry": [Fragment], "sidebar": {"sections": [SidebarSection], "module": {"path", "target", "sections"} | null}, "introduction": Description, "main_content": [Section], "examples": [ScrapedExample], "source": string | null}`,
+//! - a Description is `{"introduction": [Fragment], "sections": [{"name": Fragment, "content": [Fragment]}]}`,
+//! - a SidebarSection is `{"name", "target", "items": [{"name", "target", "kind"}]}`, targets and kinds being strings or null,
+//! - a Section is `{"id": string | null, "name": Fragment, "content": SectionContent}`, its items having ids of their own,
+//! - enums, be they Fragments, SectionContents or Herrs, are `{"kind": "snake_case_variant", "content": ...}`,
+//!   such as `{"kind": "raw", "content": "text"}`, `{"kind": "colored", "content": [Fragment, "trait"]}`
+//!   or `{"kind": "link", "content": {"content": Fragment, "href": "struct.Vec.html"}}`,
+//! - structs keep their field names, such as a Method's `{"id": "method.map", "signature": [Fragment], "description": Description}`.
+//!
+//! Deserialized Pages own all of their text.
 
 #![allow(dead_code, unused_assignments, unused_variables)]
 #![feature(let_chains)]
 
+pub mod doc_root;
 pub mod errors;
 pub mod helper_types;
+pub mod json;
+pub mod lazy;
 pub mod main_content;
+pub mod markdown;
 pub mod sidebar;
+pub mod source;
+pub mod stream;
+pub mod version;
+pub mod visit;
+pub mod visit_mut;
+
+use std::collections::HashMap;
 
 use crate::{
     errors::Herr,
     helper_types::{
         description::Description,
-        fragment::Fragment
+        fragment::Fragment,
+        html_element::HtmlElement,
+        method::NotableTrait,
+        scraped_example::ScrapedExample,
+        selector::selector,
         },
-    main_content::MainContent,
+    main_content::{
+        Anchored,
+        MainContent
+    },
     sidebar::Sidebar,
+    stream::{
+        Event,
+        PageStream,
+    },
+    version::{
+        Adapter,
+        RustdocVersion,
+    },
 };
 
+/// A page's documentation, borrowing its text from the HTML it was parsed out of.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Page<'a> {
+    /// The version of rustdoc which generated the page, if it says so.
+    pub version: Option<RustdocVersion>,
+    pub entry: Vec<Fragment<'a>>,
+    pub sidebar: Sidebar,
+    pub introduction: Description<'a>,
+    pub main_content: MainContent<'a>,
+    /// A function's "Examples found in repository", methods keeping theirs.
+    pub examples: Vec<ScrapedExample>,
+    /// Where the item's `Source` link leads, such as `../../src/core/result.rs.html#557`.
+    pub source: Option<String>,
+}
+
+impl<'a> Page<'a> {
+    /// Looks for the section or item a link such as `enum.Result.html#method.map` lands on, given its fragment.
+    pub fn find_anchor(&self, anchor: &str) -> Option<Anchored<'_, 'a>> {
+        self.main_content.find_anchor(anchor)
+    }
+}
+
+/// Kinds of pages, as found in the body's classes, which aren't about any item.
+const UNSUPPORTED_KINDS: [&str; 4] = ["help", "settings", "src", "source"];
+
+/// How forgiving the parsing of a page is.
+#[derive(Clone, Copy, Debug)]
+pub struct ParseOptions {
+    /// Fail on the first section which doesn't parse, rather than leaving it out.
+    pub strict: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions { strict: true }
+    }
+}
+
+/// A Page along with everything that had to be left out of it.
 #[derive(Debug)]
-pub struct Page {
-    entry: Vec<Fragment>,
-    sidebar: Sidebar,
-    introduction: Description,
-    main_content: MainContent,
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Parsed<'a> {
+    pub page: Page<'a>,
+    /// Only ever filled in when parsing leniently.
+    pub diagnostics: Vec<Herr>,
 }
 
 /// Converts an HTML document into a Page.
-pub fn process_html(html: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let mut sidebar: Sidebar = Sidebar(Vec::new());
-    let mut main_content = MainContent(Vec::new());
-    html.split_once("<section>")
-        .and_then(|(_head, body)| body.split_once("</section>"))
-        .and_then(|(sidebar_content, main)| {
-            sidebar = Sidebar::parse(sidebar_content);
-            main.split_once("<section id=\"main-content\" class=\"content\">")
-        })
-        .map(|(_, main_c)| {
-            if let Some((next, _)) = main_c.split_once("<script") {
-                next
-            } else {
-                main_c.trim_end_matches("</section></div></main></body></html>")
+pub fn process_html(html: &str) -> Result<Page<'_>, Herr> {
+    process_html_with(html, ParseOptions::default()).map(|parsed| parsed.page)
+}
+
+/// Converts an HTML document into as much of a Page as can be, with a diagnostic for each skipped section.
+pub fn process_html_lenient(html: &str) -> Result<Parsed<'_>, Herr> {
+    process_html_with(html, ParseOptions { strict: false })
+}
+
+/// Converts an HTML document into a Page, failing on broken sections only if strict.
+///
+/// A page which isn't an item's documentation, or is missing its main content, fails either way.
+pub fn process_html_with(html: &str, options: ParseOptions) -> Result<Parsed<'_>, Herr> {
+    let mut adapter = Adapter::default();
+    let mut body = None;
+    let mut sidebar = None;
+    let mut main = None;
+    let mut sections: Vec<(HtmlElement, Vec<HtmlElement>)> = Vec::new();
+    let mut notable_traits = HashMap::new();
+    for event in PageStream::new(html) {
+        match event {
+            Event::Version(version) => adapter = Adapter::new(Some(version)),
+            Event::Body(element) => {
+                check_body(html, &element)?;
+                body = Some(element);
             }
-        })
-        .and_then(|main_cont| main_cont.split_once("</details>"))
-        .map(|(_introduction_content, section_block)| {
-            main_content = MainContent::parse(section_block);
-            // dbg!(&main_content);
-        })
-        .ok_or(Box::new(Herr::Parsing("Can't process that page, sir")))
+            Event::Sidebar(nav) => sidebar = Some(nav),
+            Event::Preamble(element) => main = Some(element),
+            Event::Heading(heading) => sections.push((heading, Vec::new())),
+            Event::Content(element) => {
+                if let Some((_, content)) = sections.last_mut() {
+                    content.push(element);
+                }
+            }
+            Event::NotableTraits(data) => notable_traits = NotableTrait::parse_data(&data),
+            Event::MainEnd(_) => (),
+        }
+    }
+    if body.is_none() {
+        return Err(Herr::missing_section("<body>", &HtmlElement::parse_document(html)));
+    }
+    let main = main.ok_or_else(|| whole_body(html, |body| Herr::missing_section("#main-content", body)))?;
+    // sections are only parsed now, older pages telling their version past the main content
+    let sections = sections
+        .iter()
+        .map(|(heading, content)| (heading, content.iter().collect()));
+    let (mut main_content, diagnostics) = match options.strict {
+        true => (MainContent::parse_sections(sections, &adapter)?, Vec::new()),
+        false => MainContent::parse_sections_lenient(sections, &adapter),
+    };
+    let preamble = Preamble::parse(&main, sidebar.as_ref(), &adapter);
+    main_content.declare_visibilities(&preamble.entry);
+    main_content.declare_notable_traits(&notable_traits);
+
+    Ok(Parsed {
+        page: Page {
+            version: adapter.version,
+            entry: preamble.entry,
+            sidebar: preamble.sidebar,
+            introduction: preamble.introduction,
+            main_content,
+            examples: preamble.examples,
+            source: preamble.source,
+        },
+        diagnostics,
+    })
+}
+
+/// Whatever of a Page lies outside of the main content's sections, be it parsed right away or lazily.
+pub(crate) struct Preamble<'a> {
+    pub entry: Vec<Fragment<'a>>,
+    pub sidebar: Sidebar,
+    pub introduction: Description<'a>,
+    pub examples: Vec<ScrapedExample>,
+    pub source: Option<String>,
+}
+
+impl<'a> Preamble<'a> {
+    /// Parses the main content's preamble, along with the sidebar's `<nav>` if any.
+    pub fn parse(main: &HtmlElement<'a>, nav: Option<&HtmlElement>, adapter: &Adapter) -> Self {
+        Preamble {
+            entry: main
+                .select_first_with(selector!(".item-decl code"))
+                .map(|declaration| declaration.zip_content())
+                .unwrap_or_default(),
+            sidebar: nav
+                .map(|nav| Sidebar::parse(nav, adapter))
+                .unwrap_or_default(),
+            introduction: main
+                .select_first_with(selector!("details.top-doc > .docblock"))
+                .map(Description::parse)
+                .unwrap_or_default(),
+            examples: ScrapedExample::parse_all(main),
+            source: main
+                .select_first_with(selector!(".main-heading a.src, .main-heading a.srclink, .out-of-band a.srclink"))
+                .and_then(|link| link.attribute("href"))
+                .map(String::from),
+        }
+    }
+}
+
+/// Makes sure the page is an item's documentation, out of the body's start tag.
+pub(crate) fn check_body(html: &str, body: &HtmlElement) -> Result<(), Herr> {
+    let kind = match body.has_class("rustdoc") {
+        true => UNSUPPORTED_KINDS.iter().find(|kind| body.has_class(kind)).copied(),
+        false => Some("not rustdoc"),
+    };
+    match kind {
+        Some(kind) => Err(whole_body(html, |body| Herr::unsupported_page_kind(kind, body))),
+        None => Ok(()),
+    }
+}
+
+/// Builds an error out of the page's whole body, only ever built up for the error's sake.
+pub(crate) fn whole_body(html: &str, error: impl Fn(&HtmlElement) -> Herr) -> Herr {
+    let document = HtmlElement::parse_document(html);
+    let body = document.select_first_with(selector!("body")).unwrap_or(&document);
+
+    error(body)
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::{
+        main_content::test_pages::rustdoc_page,
+        Page,
+    };
+
+    #[test]
+    fn serde_round_trip() {
+        let page = rustdoc_page("struct", "\
+            <pre class=\"rust item-decl\"><code>pub struct S;</code></pre>");
+        let json = serde_json::to_string(&page).unwrap();
+        assert!(json.contains(r#""entry":[{"kind":"raw","content":"pub struct S;"}]"#));
+        let back: Page = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{:?}", back), format!("{:?}", page));
+    }
 }
@@ -1,232 +1,1204 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{
+        self,
+        Display,
+        Formatter
+    },
+};
+
 use crate::{
+    errors::Herr,
     helper_types::{
-        fragment::Fragment,
+        fragment::{
+            self,
+            inline_code,
+            write_plain,
+            Fragment
+        },
+        description::Description,
         html_element::HtmlElement,
-        method::Method
-    }
+        method::{
+            Method,
+            NotableTrait
+        },
+        selector::selector,
+    },
+    version::Adapter,
+    visit_mut::VisitMut,
 };
 
 /// The main content of a single page, from the introduction to the last of its implementation blocks.
 #[derive(Debug)]
-pub struct MainContent(pub Vec<Section>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MainContent<'a>(pub Vec<Section<'a>>);
 
 /// One of the main content's sections.
 #[derive(Debug)]
-pub struct Section {
-    pub name: Fragment,
-    pub content: SectionContent,
-}
-
-impl MainContent {
-    /// Parses the main content.
-    pub fn parse(raw: &str) -> Self {
-        let sections = raw
-            .split("<h2")
-            .skip(1)
-            .fold(Vec::new(), |mut sections, str| {
-                sections.push(format!("<h2{}", str));
-                sections
-            });
-
-        MainContent(
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Section<'a> {
+    /// The heading's id, such as `implementations`.
+    pub id: Option<Cow<'a, str>>,
+    pub name: Fragment<'a>,
+    pub content: SectionContent<'a>,
+}
+
+impl<'a> MainContent<'a> {
+    /// Parses the main content, each section running from its heading up to the next one.
+    pub fn parse(main: &HtmlElement<'a>, adapter: &Adapter) -> Result<Self, Herr> {
+        MainContent::parse_sections(MainContent::split(main, adapter), adapter)
+    }
+
+    /// Parses the main content, leaving out the sections which don't parse along with the reason why.
+    pub fn parse_lenient(main: &HtmlElement<'a>, adapter: &Adapter) -> (Self, Vec<Herr>) {
+        MainContent::parse_sections_lenient(MainContent::split(main, adapter), adapter)
+    }
+
+    /// Parses sections already split into their heading and content, as a PageStream hands them out.
+    pub fn parse_sections<'r>(
+        sections: impl IntoIterator<Item = (&'r HtmlElement<'a>, Vec<&'r HtmlElement<'a>>)>,
+        adapter: &Adapter,
+    ) -> Result<Self, Herr>
+    where
+        'a: 'r,
+    {
+        Ok(MainContent(
             sections
-                .iter()
-                .map(|section| {
-                    let mut split = section.split_inclusive("</h2>").collect::<Vec<&str>>();
-                    let name = String::from(HtmlElement::parse(&mut split[0]).content[0].raw_content());
-                    let content = match name.as_str() {
-                        "Auto Trait Implementations" => SectionContent::parse_auto_trait_implementations(split[1]),
-                        "Blanket Implementations" => SectionContent::parse_blanket_implementations(split[1]),
-                        "Fields" | "Tuple Fields" => SectionContent::parse_fields(split[1]),
-                        "Implementations" => SectionContent::parse_implementations(split[1]),
-                        "Object Safety" => SectionContent::parse_object_safety(split[1]),
-                        "Required Associated Types" => SectionContent::parse_required_associated_types(split[1]),
-                        "Trait Implementations" => SectionContent::parse_trait_implementations(split[1]),
-                        "Variants" => SectionContent::parse_variants(split[1]),
-                        _ => unreachable!()
-                    };
-                    Section {
-                        name: Fragment::Bold(Vec::from([Fragment::Raw(name)])),
-                        content,
+                .into_iter()
+                .map(|(heading, content)| Section::parse(heading, &content, adapter))
+                .collect::<Result<Vec<Section>, Herr>>()?,
+        ))
+    }
+
+    /// Parses sections already split into their heading and content, leaving out the ones which don't parse.
+    pub fn parse_sections_lenient<'r>(
+        sections: impl IntoIterator<Item = (&'r HtmlElement<'a>, Vec<&'r HtmlElement<'a>>)>,
+        adapter: &Adapter,
+    ) -> (Self, Vec<Herr>)
+    where
+        'a: 'r,
+    {
+        let mut diagnostics = Vec::new();
+        let sections = sections
+            .into_iter()
+            .filter_map(|(heading, content)| {
+                Section::parse(heading, &content, adapter)
+                    .map_err(|err| diagnostics.push(err))
+                    .ok()
+            })
+            .collect();
+
+        (MainContent(sections), diagnostics)
+    }
+
+    /// Looks for the section or item bearing the id, such as `method.map`, the leading `#` of a link being dropped.
+    pub fn find_anchor<'r>(&'r self, anchor: &str) -> Option<Anchored<'r, 'a>> {
+        let anchor = anchor.strip_prefix('#').unwrap_or(anchor);
+        let is = |id: &Option<Cow<str>>| id.as_deref() == Some(anchor);
+        let methods = |methods: &'r [Method<'a>]| methods.iter().find(|method| is(&method.id)).map(Anchored::Method);
+        self.0.iter().find_map(|section| {
+            if is(&section.id) {
+                return Some(Anchored::Section(section));
+            }
+            match &section.content {
+                SectionContent::Dummy | SectionContent::Layout(_) | SectionContent::ObjectSafety(_) => None,
+                SectionContent::Fields(fields) => fields.iter().find(|field| is(&field.id)).map(Anchored::Field),
+                SectionContent::Methods(trait_methods) => methods(trait_methods),
+                SectionContent::Implementations(implementations) => implementations.iter().find_map(|implementation| {
+                    match is(&implementation.id) {
+                        true => Some(Anchored::Implementation(implementation)),
+                        false => methods(&implementation.methods),
                     }
-                })
-                .collect(),
-        )
+                }),
+                SectionContent::RequiredAssociatedTypes(types) => types
+                    .iter()
+                    .find(|r#type| is(&r#type.id))
+                    .map(Anchored::RequiredAssociatedType),
+                SectionContent::TraitImplementations(implementations) => implementations.iter().find_map(|implementation| {
+                    match is(&implementation.id) {
+                        true => Some(Anchored::TraitImplementation(implementation)),
+                        false => methods(&implementation.methods),
+                    }
+                }),
+                SectionContent::Variants(variants) => variants.iter().find_map(|variant| match is(&variant.id) {
+                    true => Some(Anchored::Variant(variant)),
+                    false => variant.fields.iter().find(|field| is(&field.id)).map(Anchored::Field),
+                }),
+            }
+        })
+    }
+
+    /// The implementation of the trait going by that name, such as `Send`, be it a negative one or not.
+    pub fn trait_implementation(&self, name: &str) -> Option<&TraitImplementation<'a>> {
+        self.0
+            .iter()
+            .filter_map(|section| match &section.content {
+                SectionContent::TraitImplementations(implementations) => Some(implementations),
+                _ => None,
+            })
+            .flatten()
+            .find(|implementation| implementation.trait_name() == name)
+    }
+
+    /// Tells the fields' visibilities out of the item's declaration, the only place rustdoc shows them.
+    pub fn declare_visibilities(&mut self, declaration: &[Fragment]) {
+        let declaration = declaration.iter().map(Fragment::to_string).collect::<String>();
+        for section in &mut self.0 {
+            section.declare_visibilities(&declaration);
+        }
+    }
+
+    /// Hands each method the notable traits of its return type, out of the page's `notable-traits-data`.
+    pub fn declare_notable_traits(&mut self, traits: &HashMap<String, Vec<NotableTrait>>) {
+        if !traits.is_empty() {
+            NotableTraits(traits).visit_main_content_mut(self);
+        }
+    }
+
+    /// Splits the main content into each section's heading and content.
+    fn split<'r>(main: &'r HtmlElement<'a>, adapter: &Adapter) -> Vec<(&'r HtmlElement<'a>, Vec<&'r HtmlElement<'a>>)> {
+        let mut sections: Vec<(&HtmlElement, Vec<&HtmlElement>)> = Vec::new();
+        for element in &main.inner_elements {
+            if adapter.is_section_heading(element) {
+                sections.push((element, Vec::new()));
+            } else if let Some((_, content)) = sections.last_mut() {
+                content.push(element);
+            }
+        }
+
+        sections
+    }
+}
+
+impl<'a> Section<'a> {
+    /// Parses a single section, according to its name.
+    pub fn parse(heading: &HtmlElement<'a>, content: &[&HtmlElement<'a>], adapter: &Adapter) -> Result<Self, Herr> {
+        let name = Section::name(heading);
+        if content.is_empty() {
+            return Err(Herr::missing_section("the section's content", heading));
+        }
+        let content = match adapter.section_name(&name) {
+            "Auto Trait Implementations" | "Auto implementors" => SectionContent::parse_auto_trait_implementations(content, adapter)?,
+            "Blanket Implementations" => SectionContent::parse_blanket_implementations(content, adapter)?,
+            "Dyn Compatibility" => SectionContent::parse_object_safety(content)?,
+            "Fields" | "Tuple Fields" => SectionContent::parse_fields(content)?,
+            "Implementations" => SectionContent::parse_implementations(content, adapter)?,
+            "Implementors" => SectionContent::parse_trait_implementations(content, adapter)?,
+            "Layout" => SectionContent::parse_layout(content)?,
+            "Provided Methods" | "Required Methods" => SectionContent::parse_methods(content, adapter)?,
+            "Required Associated Types" => SectionContent::parse_required_associated_types(content, adapter)?,
+            "Trait Implementations" => SectionContent::parse_trait_implementations(content, adapter)?,
+            "Variants" => SectionContent::parse_variants(content, adapter)?,
+            _ => SectionContent::Dummy,
+        };
+
+        Ok(Section {
+            id: anchor(heading),
+            name: Fragment::Bold(Vec::from([Fragment::Raw(Cow::Owned(name))])),
+            content,
+        })
+    }
+
+    /// Reads the section's name off its heading.
+    pub fn name(heading: &HtmlElement) -> String {
+        let name = heading
+            .content
+            .iter()
+            .filter_map(|raw_content| raw_content.raw_content())
+            .collect::<String>();
+
+        String::from(name.trim())
+    }
+}
+
+impl Section<'_> {
+    /// Tells the visibilities of a Fields section out of the item's declaration, in plain text.
+    pub fn declare_visibilities(&mut self, declaration: &str) {
+        if let SectionContent::Fields(fields) = &mut self.content {
+            let visibilities = visibilities(declaration);
+            for field in fields {
+                if let Some((_, visibility)) = visibilities.iter().find(|(name, _)| *name == field.name) {
+                    field.visibility = visibility.clone();
+                }
+            }
+        }
+    }
+
+    /// Hands each of the section's methods the notable traits of its return type.
+    pub fn declare_notable_traits(&mut self, traits: &HashMap<String, Vec<NotableTrait>>) {
+        if !traits.is_empty() {
+            NotableTraits(traits).visit_section_mut(self);
+        }
+    }
+
+    /// Renders the section as Markdown, under a heading bearing its name.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("## {}", self.name);
+        // descriptions running over several lines stay within their list item
+        let entry = |name: &str, description: &str| match description.trim() {
+            "" => format!("\n- {}", inline_code(name)),
+            description => format!("\n- {} : {}", inline_code(name), description.replace('\n', "\n  ")),
+        };
+        let block = |header: &Fragment, methods: &[Method]| {
+            let methods = methods.iter().map(Method::to_markdown).collect::<Vec<String>>();
+            format!("\n\n### {}\n\n{}", inline_code(&header.to_string()), methods.join("\n\n"))
+        };
+        match &self.content {
+            SectionContent::Dummy => (),
+            SectionContent::Fields(fields) => {
+                markdown.push('\n');
+                for field in fields {
+                    markdown.push_str(&entry(&field.to_string(), &field.description.to_markdown()));
+                }
+            }
+            SectionContent::Implementations(implementations) => {
+                for implementation in implementations {
+                    markdown.push_str(&block(&implementation.inherent_impl, &implementation.methods));
+                }
+            }
+            SectionContent::Methods(methods) => {
+                let methods = methods.iter().map(Method::to_markdown).collect::<Vec<String>>();
+                markdown.push_str(&format!("\n\n{}", methods.join("\n\n")));
+            }
+            SectionContent::Layout(layout) => {
+                markdown.push('\n');
+                for (name, value) in layout.entries() {
+                    markdown.push_str(&format!("\n- {} : {}", name, value));
+                }
+            }
+            SectionContent::ObjectSafety(fragments) => {
+                markdown.push_str(&format!("\n\n{}", fragment::to_markdown(fragments)));
+            }
+            SectionContent::RequiredAssociatedTypes(types) => {
+                markdown.push('\n');
+                for r#type in types {
+                    markdown.push_str(&entry(&r#type.name.to_string(), &r#type.description.to_markdown()));
+                }
+            }
+            SectionContent::TraitImplementations(implementations) => {
+                for implementation in implementations {
+                    markdown.push_str(&block(&implementation.trait_impl, &implementation.methods));
+                }
+            }
+            SectionContent::Variants(variants) => {
+                markdown.push('\n');
+                for variant in variants {
+                    markdown.push_str(&entry(&variant.name.to_string(), &variant.description.to_markdown()));
+                    for field in &variant.fields {
+                        let field = entry(&field.to_string(), &field.description.to_markdown());
+                        markdown.push_str(&field.replace('\n', "\n  "));
+                    }
+                }
+            }
+        }
+
+        String::from(markdown.trim())
+    }
+}
+
+/// Plain text, the section's name followed by each of its entries.
+impl Display for Section<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        match &self.content {
+            SectionContent::Dummy => Ok(()),
+            SectionContent::Fields(fields) => fields
+                .iter()
+                .try_for_each(|field| write_entry(f, field, &field.description)),
+            SectionContent::Implementations(implementations) => implementations
+                .iter()
+                .try_for_each(|implementation| write_block(f, &implementation.inherent_impl, &implementation.methods)),
+            SectionContent::Methods(methods) => methods.iter().try_for_each(|method| write!(f, "\n\n{}", method)),
+            SectionContent::Layout(layout) => {
+                f.write_str("\n")?;
+                layout
+                    .entries()
+                    .iter()
+                    .try_for_each(|(name, value)| write!(f, "\n{}: {}", name, value))
+            }
+            SectionContent::ObjectSafety(fragments) => {
+                f.write_str("\n\n")?;
+                write_plain(f, fragments)
+            }
+            SectionContent::RequiredAssociatedTypes(types) => types
+                .iter()
+                .try_for_each(|r#type| write_entry(f, &r#type.name, &r#type.description)),
+            SectionContent::TraitImplementations(implementations) => implementations
+                .iter()
+                .try_for_each(|implementation| write_block(f, &implementation.trait_impl, &implementation.methods)),
+            SectionContent::Variants(variants) => variants.iter().try_for_each(|variant| {
+                write_entry(f, &variant.name, &variant.description)?;
+                variant
+                    .fields
+                    .iter()
+                    .try_for_each(|field| write_entry(f, field, &field.description))
+            }),
+        }
+    }
+}
+
+impl TypeLayout {
+    /// Each of the layout's lines, such as `("Size", "24 bytes")`, variants coming last.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let bytes = |size: Option<u64>, is_unsized: bool, uninhabited: bool| {
+            let size = match (size, is_unsized) {
+                (_, true) => String::from("unsized"),
+                (Some(1), _) => String::from("1 byte"),
+                (Some(size), _) => format!("{} bytes", size),
+                (None, _) => String::from("unknown"),
+            };
+            match uninhabited {
+                true => size + " (uninhabited)",
+                false => size,
+            }
+        };
+        let mut entries = Vec::new();
+        if let Some(note) = &self.note {
+            entries.push((String::from("Note"), note.clone()));
+        }
+        if self.size.is_some() || self.is_unsized {
+            entries.push((String::from("Size"), bytes(self.size, self.is_unsized, self.uninhabited)));
+        }
+        if let Some(align) = self.align {
+            entries.push((String::from("Alignment"), bytes(Some(align), false, false)));
+        }
+        for variant in &self.variants {
+            entries.push((format!("Size of {}", variant.name), bytes(variant.size, false, variant.uninhabited)));
+        }
+
+        entries
     }
 }
 
+/// The field as declared, such as `len: usize`.
+impl Display for Field<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.name)?;
+        // fields whose heading leaves their type out
+        if self.r#type.is_empty() {
+            return Ok(());
+        }
+        f.write_str(": ")?;
+        write_plain(f, &self.r#type)
+    }
+}
+
+/// Writes one of a section's entries, on top of its description if any.
+fn write_entry(f: &mut Formatter, name: &dyn Display, description: &dyn Display) -> fmt::Result {
+    let description = description.to_string();
+    write!(f, "\n\n{}", name)?;
+    if !description.is_empty() {
+        write!(f, "\n{}", description)?;
+    }
+
+    Ok(())
+}
+
+/// Writes an implementation block's header, followed by each of its methods.
+fn write_block(f: &mut Formatter, header: &Fragment, methods: &[Method]) -> fmt::Result {
+    write!(f, "\n\n{}", header)?;
+    methods.iter().try_for_each(|method| write!(f, "\n\n{}", method))
+}
+
+
+/// Whichever node of the main content an anchor leads to.
+#[derive(Clone, Copy, Debug)]
+pub enum Anchored<'r, 'a> {
+    Section(&'r Section<'a>),
+    Field(&'r Field<'a>),
+    Implementation(&'r Implementation<'a>),
+    Method(&'r Method<'a>),
+    RequiredAssociatedType(&'r RequiredAssociatedType<'a>),
+    TraitImplementation(&'r TraitImplementation<'a>),
+    Variant(&'r Variant<'a>),
+}
+
 /// Represents the content of one of the MainContent's Sections.
 #[derive(Debug)]
-pub enum SectionContent {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "content", rename_all = "snake_case"))]
+pub enum SectionContent<'a> {
     Dummy,
-    Fields(Vec<Field>),
-    Implementations(Vec<Implementation>),
-    ObjectSafety(Vec<Fragment>),
-    RequiredAssociatedTypes(Vec<RequiredAssociatedType>),
-    TraitImplementations(Vec<TraitImplementation>),
-    Variants(Vec<Variant>),
+    Fields(Vec<Field<'a>>),
+    Implementations(Vec<Implementation<'a>>),
+    Layout(TypeLayout),
+    /// A trait's Required or Provided Methods, the section's name telling which.
+    Methods(Vec<Method<'a>>),
+    ObjectSafety(Vec<Fragment<'a>>),
+    RequiredAssociatedTypes(Vec<RequiredAssociatedType<'a>>),
+    TraitImplementations(Vec<TraitImplementation<'a>>),
+    Variants(Vec<Variant<'a>>),
 }
 
-/// Represents a single struct field.
+/// Represents a single field, of a struct, a union or a variant.
 #[derive(Debug)]
-pub struct Field {
-    pub content: Vec<Fragment>,
-    pub description: Vec<Fragment>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Field<'a> {
+    /// Such as `structfield.len`.
+    pub id: Option<Cow<'a, str>>,
+    /// Such as `len`, or `0` for a tuple field.
+    pub name: String,
+    pub r#type: Vec<Fragment<'a>>,
+    /// Only the item's declaration tells, fields being public until it says otherwise.
+    pub visibility: Visibility,
+    /// The deprecation notice, if any.
+    pub deprecation: Option<Vec<Fragment<'a>>>,
+    pub description: Description<'a>,
+}
+
+/// Who a field is visible to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "content", rename_all = "snake_case"))]
+pub enum Visibility {
+    #[default]
+    Public,
+    /// Such as `pub(crate)` or `pub(in crate::shapes)`.
+    Restricted(String),
+    /// Only shown with `--document-private-items`.
+    Private,
 }
 
 /// Represents a single implementation for one specific type.
 #[derive(Debug)]
-pub struct Implementation {
-    pub inherent_impl: Fragment,
-    pub methods: Vec<Method>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Implementation<'a> {
+    /// Such as `impl-Result%3CT,+E%3E`.
+    pub id: Option<Cow<'a, str>>,
+    pub inherent_impl: Fragment<'a>,
+    pub methods: Vec<Method<'a>>,
 }
 
 /// Represents one of a trait's required associated types.
 #[derive(Debug)]
-pub struct RequiredAssociatedType {
-    pub name: Fragment,
-    pub description: Fragment,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequiredAssociatedType<'a> {
+    /// Such as `associatedtype.Item`.
+    pub id: Option<Cow<'a, str>>,
+    pub name: Fragment<'a>,
+    pub description: Fragment<'a>,
 }
 
 /// Represents a single trait implementation for one specific type.
 #[derive(Debug)]
-pub struct TraitImplementation {
-    pub trait_impl: Fragment,
-    pub methods: Vec<Method>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraitImplementation<'a> {
+    /// Such as `impl-Clone-for-Result%3CT,+E%3E`.
+    pub id: Option<Cow<'a, str>>,
+    pub trait_impl: Fragment<'a>,
+    pub kind: TraitImplKind,
+    /// Whether the type does NOT implement the trait, as in `impl<T> !Send for Rc<T>`.
+    pub negative: bool,
+    /// The impl block's generic parameters, such as `T: ?Sized`.
+    pub generics: Vec<String>,
+    /// Such as `From<T>`, without the `!` of a negative impl.
+    pub trait_path: String,
+    /// The type implementing the trait, such as `Rc<T, A>`.
+    pub self_type: String,
+    /// Each of the where clause's predicates, such as `T: Send`.
+    pub where_clause: Vec<String>,
+    pub methods: Vec<Method<'a>>,
+}
+
+/// Where a trait implementation comes from, which tells the section it lies in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TraitImplKind {
+    /// Written down, be it by hand or by a derive.
+    Trait,
+    /// An auto trait's, such as `Send` or `Unpin`, which the compiler implements by itself.
+    Synthetic,
+    /// Implemented for every type meeting some bounds, such as `impl<T> From<T> for T`.
+    Blanket,
 }
 
 /// Represents one of an enum's variants.
 #[derive(Debug)]
-pub struct Variant {
-    pub name: Fragment,
-    pub description: Vec<Fragment>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variant<'a> {
+    /// Such as `variant.Ok`.
+    pub id: Option<Cow<'a, str>>,
+    pub name: Fragment<'a>,
+    pub kind: VariantKind,
+    /// Only those rustdoc documents, a tuple variant's undocumented fields being left out.
+    pub fields: Vec<Field<'a>>,
+    /// Such as `-1` out of `Less = -1`.
+    pub discriminant: Option<String>,
+    pub description: Description<'a>,
 }
 
-impl SectionContent {
-    /// Parses the fields of a struct, be they named or tuple fields.
-    pub fn parse_fields(data: &str) -> SectionContent {
-        SectionContent::Fields(
-            data.split_terminator("</div>")
-                .map(|field| {
-                    let (_content, _description) = field.split_once("</span>").unwrap();
-                    Field {
-                        content: HtmlElement::extract("code", _content).zip_content(),
-                        description: HtmlElement::extract("p", _description).zip_content(),
+/// The size of a type, as told by docs built with `--show-type-layout`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeLayout {
+    /// In bytes, unless unsized or left unknown.
+    pub size: Option<u64>,
+    /// In bytes, for the rustdocs which tell it.
+    pub align: Option<u64>,
+    pub is_unsized: bool,
+    pub uninhabited: bool,
+    /// An enum's size for each of its variants.
+    pub variants: Vec<VariantLayout>,
+    /// Why the layout is missing, such as the type being too generic.
+    pub note: Option<String>,
+}
+
+/// The size of one of an enum's variants, in bytes.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariantLayout {
+    pub name: String,
+    pub size: Option<u64>,
+    pub uninhabited: bool,
+}
+
+/// Whether a variant holds nothing, a tuple or named fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum VariantKind {
+    Unit,
+    Tuple,
+    Struct,
+}
+
+impl<'a> SectionContent<'a> {
+    /// Parses the Layout section, its size and alignment being paragraphs, and its variants' sizes a list.
+    ///
+    /// The note about layouts being unstable is left out.
+    pub fn parse_layout(content: &[&HtmlElement<'a>]) -> Result<SectionContent<'a>, Herr> {
+        let mut layout = TypeLayout::default();
+        for element in content.iter().flat_map(|docblock| docblock.inner_elements.iter()) {
+            let text = |element: &HtmlElement| element.text().split_whitespace().collect::<Vec<&str>>().join(" ");
+            match element.kind.as_ref() {
+                "p" => {
+                    let text = text(element);
+                    if let Some(size) = text.strip_prefix("Size:") {
+                        (layout.size, layout.is_unsized, layout.uninhabited) = layout_size(size);
+                    } else if let Some(align) = text.strip_prefix("Alignment:") {
+                        layout.align = layout_size(align).0;
+                    } else if let Some(note) = text.strip_prefix("Note:") {
+                        layout.note = Some(String::from(note.trim()));
                     }
+                }
+                "ul" => layout.variants.extend(element.select_with(selector!("li")).into_iter().filter_map(|variant| {
+                    let text = text(variant);
+                    let (name, size) = text.split_once(':')?;
+                    let (size, _, uninhabited) = layout_size(size);
+                    Some(VariantLayout {
+                        name: String::from(name.trim()),
+                        size,
+                        uninhabited,
+                    })
+                })),
+                _ => (),
+            }
+        }
+
+        Ok(SectionContent::Layout(layout))
+    }
+
+    /// Parses the fields of a struct, be they named or tuple fields.
+    ///
+    /// Each field's header is followed by its stability notes and its docblock.
+    pub fn parse_fields(content: &[&HtmlElement<'a>]) -> Result<SectionContent<'a>, Herr> {
+        Ok(SectionContent::Fields(
+            content
+                .iter()
+                .enumerate()
+                .filter(|(_, element)| element.has_class("structfield"))
+                .map(|(index, field)| {
+                    let following = content[index + 1..]
+                        .iter()
+                        .copied()
+                        .take_while(|element| !element.has_class("structfield"));
+                    Field::parse(field, following)
                 })
                 .collect(),
-        )
+        ))
     }
     /// Parses the Implementations section.
-    pub fn parse_implementations(data: &str) -> SectionContent {
-        Self::Implementations(
-            data.split("<details class=\"toggle implementors-toggle\" open>")
-                .skip(1)
-                .map(|imp| {
-                    let (unfiltered_content, _methods) = imp.split_once("</summary>").unwrap();
-                    let inherent_impl =
-                        Fragment::Bold(HtmlElement::extract("h3", unfiltered_content).zip_content());
-                    let methods = _methods
-                        .trim_end_matches("</div></details>")
-                        .split("<details class=\"toggle method-toggle\" open>")
-                        .skip(1)
-                        .map(|method| Method::parse(method))
-                        .collect();
-                    Implementation {
+    pub fn parse_implementations(content: &[&HtmlElement<'a>], adapter: &Adapter) -> Result<SectionContent<'a>, Herr> {
+        Ok(Self::Implementations(
+            impl_blocks(content, adapter)
+                .map(|block| {
+                    let (id, inherent_impl, methods) = block?;
+                    Ok(Implementation {
+                        id,
                         inherent_impl,
                         methods,
-                    }
+                    })
                 })
-                .collect(),
-        )
+                .collect::<Result<Vec<Implementation>, Herr>>()?,
+        ))
     }
     /// Parses a type's auto Trait Implementations.
-    pub fn parse_auto_trait_implementations(data: &str) -> Self {
-        Self::TraitImplementations(
-            data.split("</section>")
-                .filter(|split| *split != "</div>")
-                .map(|implementation| TraitImplementation {
-                    trait_impl: Fragment::Bold(HtmlElement::extract("h3", implementation).zip_content()),
-                    methods: Vec::new(),
-                })
-                .collect(),
-        )
+    pub fn parse_auto_trait_implementations(content: &[&HtmlElement<'a>], adapter: &Adapter) -> Result<Self, Herr> {
+        Self::parse_impls_of_kind(content, adapter, TraitImplKind::Synthetic)
     }
     /// Parses a type's blanket Trait Implementations.
-    pub fn parse_blanket_implementations(data: &str) -> SectionContent {
-        Self::TraitImplementations(
-            data.split("<details class=\"toggle implementors-toggle\">")
-                .skip(1)
-                .map(|imp| {
-                    let (unfiltered_content, _methods) = imp.split_once("</summary>").unwrap();
-                    let trait_impl =
-                        Fragment::Bold(HtmlElement::extract("h3", unfiltered_content).zip_content());
-                    let methods = _methods
-                        .trim_end_matches("</div></details>")
-                        .split("<details class=\"toggle method-toggle\">")
-                        .skip(1)
-                        .map(|method| Method::parse(method))
-                        .collect();
-                    TraitImplementation {
-                        trait_impl,
-                        methods,
-                    }
-                })
-                .collect(),
-        )
+    pub fn parse_blanket_implementations(content: &[&HtmlElement<'a>], adapter: &Adapter) -> Result<SectionContent<'a>, Herr> {
+        Self::parse_impls_of_kind(content, adapter, TraitImplKind::Blanket)
     }
     /// Parses a type's Trait Implementations.
-    pub fn parse_trait_implementations(data: &str) -> SectionContent {
-        Self::TraitImplementations(
-            data.split("</details>")
-                .skip(1)
-                .filter(|split| *split != "</div>")
-                .map(|_impl| {
-                    let trait_impl = Fragment::Bold(HtmlElement::extract("h3", _impl).zip_content());
-                    let methods = if let Some((_, _methods))= _impl.split_once("</summary>") {
-                        _methods
-                            .trim_start_matches("<div class=\"impl-items\">")
-                            .split_inclusive("<details")
-                            .filter(|split| *split != "<details")
-                            .map(|method| Method::parse(method))
-                            .collect()
-                    } else {
-                        Vec::new()
-                    };
-                    TraitImplementation {
-                        trait_impl,
-                        methods,
-                    }
+    pub fn parse_trait_implementations(content: &[&HtmlElement<'a>], adapter: &Adapter) -> Result<SectionContent<'a>, Herr> {
+        Self::parse_impls_of_kind(content, adapter, TraitImplKind::Trait)
+    }
+    /// Parses trait implementations, the section they lie in telling where they come from.
+    fn parse_impls_of_kind(content: &[&HtmlElement<'a>], adapter: &Adapter, kind: TraitImplKind) -> Result<SectionContent<'a>, Herr> {
+        Ok(Self::TraitImplementations(
+            impl_blocks(content, adapter)
+                .map(|block| {
+                    let (id, trait_impl, methods) = block?;
+                    Ok(TraitImplementation::new(id, trait_impl, kind, methods))
                 })
+                .collect::<Result<Vec<TraitImplementation>, Herr>>()?,
+        ))
+    }
+    /// Parses the optional Dyn Compatibility section, formerly Object Safety.
+    pub fn parse_object_safety(content: &[&HtmlElement<'a>]) -> Result<SectionContent<'a>, Herr> {
+        Ok(Self::ObjectSafety(
+            content
+                .iter()
+                .flat_map(|element| element.inner_elements.iter())
+                .flat_map(|paragraph| paragraph.zip_content())
                 .collect(),
-        )
+        ))
     }
-    /// Parses the optional Object Safety section.
-    pub fn parse_object_safety(mut data: &str) -> SectionContent {
-        Self::ObjectSafety(HtmlElement::parse(&mut data).zip_content())
+    /// Parses a Trait's Required or Provided Methods, be they toggles or lone sections.
+    pub fn parse_methods(content: &[&HtmlElement<'a>], adapter: &Adapter) -> Result<SectionContent<'a>, Herr> {
+        Ok(Self::Methods(
+            content
+                .iter()
+                .flat_map(|methods| methods.inner_elements.iter())
+                .filter(|method| adapter.is_toggle(method) || method.has_class("method"))
+                .map(|method| Method::parse(method, adapter))
+                .collect::<Result<Vec<Method>, Herr>>()?,
+        ))
     }
     /// Parses a Trait's optional required associated types.
-    pub fn parse_required_associated_types(data: &str) -> SectionContent {
-        Self::RequiredAssociatedTypes(
-            data.trim_end_matches("</div>")
-                .split_terminator("</details>")
-                .map(|associated_type| RequiredAssociatedType {
-                    name: Fragment::Bold(HtmlElement::extract("h4", associated_type).zip_content()),
-                    description: HtmlElement::extract("p", associated_type).content[0].clone(),
+    pub fn parse_required_associated_types(content: &[&HtmlElement<'a>], adapter: &Adapter) -> Result<SectionContent<'a>, Herr> {
+        Ok(Self::RequiredAssociatedTypes(
+            content
+                .iter()
+                .flat_map(|methods| methods.inner_elements.iter())
+                .filter(|associated_type| matches!(associated_type.kind.as_ref(), "details" | "section"))
+                .map(|associated_type| {
+                    Ok(RequiredAssociatedType {
+                        id: anchor(associated_type),
+                        name: Fragment::Bold(code_header(associated_type, adapter)?),
+                        description: Fragment::Raw(
+                            associated_type
+                                .select_first_with(selector!(".docblock p"))
+                                .map(|paragraph| paragraph.text())
+                                .unwrap_or_default(),
+                        ),
+                    })
                 })
-                .collect(),
-        )
+                .collect::<Result<Vec<RequiredAssociatedType>, Herr>>()?,
+        ))
     }
     /// Parses an Enum's Variants.
-    pub fn parse_variants(data: &str) -> SectionContent {
-        Self::Variants(
-            data.split("</a>")
-                .skip(1)
-                .map(|variant| Variant {
-                    name: Fragment::Bold(HtmlElement::extract("h3", variant).zip_content()),
-                    description: HtmlElement::extract("p", variant).zip_content(),
-                })
+    ///
+    /// Each variant's header is followed by its stability notes, its docblock and the docs of its fields, in that order.
+    pub fn parse_variants(content: &[&HtmlElement<'a>], adapter: &Adapter) -> Result<SectionContent<'a>, Herr> {
+        let elements = adapter.variants(content);
+        let mut variants = Vec::new();
+        for (index, variant) in elements.iter().enumerate() {
+            if variant.has_class("variant") {
+                let following = elements[index + 1..]
+                    .iter()
+                    .take_while(|element| !element.has_class("variant"));
+                variants.push(Variant::parse(variant, following, adapter)?);
+            }
+        }
+
+        Ok(Self::Variants(variants))
+    }
+}
+
+impl<'a> TraitImplementation<'a> {
+    /// Builds a trait implementation, taking its header apart.
+    pub fn new(id: Option<Cow<'a, str>>, trait_impl: Fragment<'a>, kind: TraitImplKind, methods: Vec<Method<'a>>) -> Self {
+        let header = trait_impl.to_string();
+        let header = header.split_whitespace().collect::<Vec<&str>>().join(" ");
+        // the where clause's `<div>` comes right after the self type, with no space in between
+        let mut parts = split_outside(&header, "where ").into_iter();
+        let header = parts.next().unwrap_or_default();
+        let where_clause = parts.next().unwrap_or_default();
+        let header = header.strip_prefix("unsafe ").unwrap_or(header);
+        let header = header.strip_prefix("impl").unwrap_or(header);
+        // the generic parameters run up to the first closing bracket outside of any other
+        let (generics, header) = match header.strip_prefix('<') {
+            Some(rest) => {
+                let end = split_outside(rest, ">").first().map_or(0, |generics| generics.len());
+                (&rest[..end], rest.get(end + 1..).unwrap_or_default())
+            }
+            None => ("", header),
+        };
+        let mut parts = split_outside(header.trim(), " for ").into_iter();
+        let trait_path = parts.next().unwrap_or_default();
+        let self_type = parts.next().unwrap_or_default();
+        let list = |text: &str| {
+            split_outside(text, ",")
+                .into_iter()
+                .map(str::trim)
+                .filter(|part| !part.is_empty())
+                .map(String::from)
+                .collect::<Vec<String>>()
+        };
+
+        TraitImplementation {
+            id,
+            trait_impl,
+            kind,
+            negative: trait_path.starts_with('!'),
+            generics: list(generics),
+            trait_path: String::from(trait_path.trim_start_matches('!').trim()),
+            self_type: String::from(self_type.trim()),
+            where_clause: list(where_clause),
+            methods,
+        }
+    }
+
+    /// The trait's name, such as `From` out of `From<T>`.
+    pub fn trait_name(&self) -> &str {
+        let path = self.trait_path.split('<').next().unwrap_or_default();
+        path.rsplit("::").next().unwrap_or(path)
+    }
+}
+
+impl<'a> Field<'a> {
+    /// Parses a field out of its header, `name: Type` within a `<code>`, and the elements following it.
+    fn parse<'r>(header: &HtmlElement<'a>, following: impl Iterator<Item = &'r HtmlElement<'a>>) -> Self
+    where
+        'a: 'r,
+    {
+        let mut deprecation = None;
+        let mut description = None;
+        for element in following {
+            if element.has_class("item-info") {
+                // the notice comes after an emoji
+                deprecation = element
+                    .select_first_with(selector!(".stab.deprecated"))
+                    .map(|notice| notice.select_first_with(selector!("span:not(.emoji)")).unwrap_or(notice).zip_content());
+            } else if element.has_class("docblock") && description.is_none() {
+                description = Some(Description::parse(element));
+            }
+        }
+        // hidden and private fields may come without their <code>
+        let mut fragments = header
+            .select_first_with(selector!("code"))
+            .map(|code| code.zip_content())
+            .unwrap_or_else(|| header.zip_content());
+        let text = fragments.iter().map(Fragment::to_string).collect::<String>();
+        let id = anchor(header);
+        let name = match id.as_deref().and_then(|id| id.rsplit_once('.')) {
+            Some((_, name)) => String::from(name),
+            None => String::from(text.split(':').next().unwrap_or_default().trim()),
+        };
+        // the type follows the name within the first piece of raw text
+        if let Some(Fragment::Raw(first)) = fragments.first_mut()
+            && let Some((_, rest)) = first.split_once(':')
+        {
+            let start = first.len() - rest.trim_start().len();
+            *first = match &*first {
+                Cow::Borrowed(text) => {
+                    let text: &'a str = text;
+                    Cow::Borrowed(&text[start..])
+                }
+                Cow::Owned(text) => Cow::Owned(String::from(&text[start..])),
+            };
+            if first.is_empty() {
+                fragments.remove(0);
+            }
+        } else if !text.contains(':') {
+            fragments.clear();
+        }
+
+        Field {
+            id,
+            name,
+            r#type: fragments,
+            visibility: Visibility::Public,
+            deprecation,
+            description: description.unwrap_or_default(),
+        }
+    }
+}
+
+impl<'a> Variant<'a> {
+    /// Parses a variant out of its header and the elements following it.
+    fn parse<'r>(
+        variant: &HtmlElement<'a>,
+        following: impl Iterator<Item = &'r &'r HtmlElement<'a>>,
+        adapter: &Adapter,
+    ) -> Result<Self, Herr>
+    where
+        'a: 'r,
+    {
+        let name = code_header(variant, adapter)?;
+        let mut description = None;
+        let mut fields = None;
+        for element in following {
+            if element.has_class("docblock") && description.is_none() {
+                description = Some(Description::parse(element));
+            } else if element.has_class("sub-variant") {
+                fields = Some(element);
+            }
+        }
+        let header = name.iter().map(Fragment::to_string).collect::<String>();
+        let rest = header.trim_start_matches(|character: char| character.is_alphanumeric() || character == '_');
+        let kind = match (fields.and_then(|fields| fields.select_first_with(selector!("h4"))), rest.trim_start().chars().next()) {
+            (Some(heading), _) if heading.text().contains("Tuple") => VariantKind::Tuple,
+            (Some(_), _) | (None, Some('{')) => VariantKind::Struct,
+            (None, Some('(')) => VariantKind::Tuple,
+            (None, _) => VariantKind::Unit,
+        };
+
+        Ok(Variant {
+            id: anchor(variant),
+            name: Fragment::Bold(name),
+            kind,
+            fields: fields
+                .map(|fields| fields.select_with(selector!(".sub-variant-field")))
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|field| field.inner_elements.split_first())
+                .map(|(header, following)| Field::parse(header, following.iter()))
                 .collect(),
-        )
+            discriminant: (kind == VariantKind::Unit)
+                .then(|| rest.split_once('=').map(|(_, value)| String::from(value.trim())))
+                .flatten(),
+            description: description.unwrap_or_default(),
+        })
+    }
+}
+
+/// Returns the header of an item - its signature, an impl block's header - as fragments.
+pub fn code_header<'a>(item: &HtmlElement<'a>, adapter: &Adapter) -> Result<Vec<Fragment<'a>>, Herr> {
+    item.select_first_with(adapter.code_header())
+        .map(|header| header.zip_content())
+        .ok_or_else(|| Herr::unexpected_tag("a .code-header", item))
+}
+
+/// Returns the id an item can be linked to by, be it on the item itself or on the header of its toggle.
+pub fn anchor<'a>(item: &HtmlElement<'a>) -> Option<Cow<'a, str>> {
+    let id = |element: &HtmlElement<'a>| {
+        element
+            .attributes()
+            .iter()
+            .find(|(name, _)| name == "id")
+            .map(|(_, id)| id.clone())
+    };
+    id(item).or_else(|| item.select_first_with(selector!("summary > [id]")).and_then(id))
+}
+
+/// Reads the visibility of each field off a struct's or a union's declaration, such as `pub struct S(pub u8, _);`.
+fn visibilities(declaration: &str) -> Vec<(String, Visibility)> {
+    let Some(start) = declaration.find("struct ").or_else(|| declaration.find("union ")) else {
+        return Vec::new();
+    };
+    let mut depth = 0;
+    let mut previous = ' ';
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut tuple = None;
+    // a where clause coming before braced fields, its bounds having brackets of their own
+    let mut word = String::new();
+    let mut in_where = false;
+    for character in declaration[start..].chars() {
+        if depth == 0 && tuple.is_none() {
+            match character.is_alphanumeric() || character == '_' {
+                true => word.push(character),
+                false => in_where |= std::mem::take(&mut word) == "where",
+            }
+        }
+        match (character, tuple) {
+            // the fields start with the first bracket past the generics, or the brace past the where clause
+            ('(' | '{', None) if depth == 0 && !(in_where && character == '(') => {
+                tuple = Some(character == '(');
+                depth += 1;
+                continue;
+            }
+            ('<' | '(' | '[' | '{', _) => depth += 1,
+            ('>', _) if previous == '-' => (),
+            ('>' | ')' | ']' | '}', _) => depth -= 1,
+            (',', Some(_)) if depth == 1 => {
+                fields.push(std::mem::take(&mut field));
+                continue;
+            }
+            _ => (),
+        }
+        previous = character;
+        if depth == 0 && tuple.is_some() {
+            fields.push(std::mem::take(&mut field));
+            break;
+        }
+        if tuple.is_some() {
+            field.push(character);
+        }
+    }
+    let visibility = |prefix: &str| match prefix.trim() {
+        "" => Visibility::Private,
+        "pub" => Visibility::Public,
+        prefix => Visibility::Restricted(String::from(prefix)),
+    };
+
+    fields
+        .iter()
+        .map(|field| field.trim())
+        .filter(|field| !field.is_empty() && !field.starts_with("/*"))
+        .enumerate()
+        .filter_map(|(index, field)| match tuple {
+            Some(true) if field == "_" => None,
+            Some(true) => {
+                let prefix = match field.strip_prefix("pub") {
+                    Some(rest) if rest.starts_with('(') => &field[..field.find(')').map_or(field.len(), |end| end + 1)],
+                    Some(rest) if rest.starts_with(char::is_whitespace) => "pub",
+                    _ => "",
+                };
+                Some((index.to_string(), visibility(prefix)))
+            }
+            _ => {
+                let (declared, _) = field.split_once(':')?;
+                // long declarations hide their fields behind a "Show 18 fields" toggle
+                let declared = declared.lines().last().unwrap_or_default().trim();
+                let (prefix, name) = declared.rsplit_once(' ').unwrap_or(("", declared));
+                Some((String::from(name), visibility(prefix)))
+            }
+        })
+        .collect()
+}
+
+/// Splits the text on each separator lying outside of any brackets, the `>` of `->` being no bracket.
+///
+/// A separator starting with a letter has to start a word too.
+fn split_outside<'t>(text: &'t str, separator: &str) -> Vec<&'t str> {
+    let is_word = |character: char| character.is_alphanumeric() || character == '_';
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut previous = ' ';
+    for (index, character) in text.char_indices() {
+        if depth == 0
+            && index >= start
+            && text[index..].starts_with(separator)
+            && !(separator.starts_with(is_word) && is_word(previous))
+            && !(character == '>' && previous == '-')
+        {
+            parts.push(&text[start..index]);
+            start = index + separator.len();
+            previous = character;
+            continue;
+        }
+        match character {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' if previous == '-' => (),
+            '>' | ')' | ']' | '}' => depth -= 1,
+            _ => (),
+        }
+        previous = character;
+    }
+    parts.push(&text[start..]);
+
+    parts
+}
+
+/// Reads a size such as `24 bytes`, `1 byte`, `(unsized)` or `0 bytes (uninhabited)`, telling whether it is unsized or uninhabited.
+fn layout_size(text: &str) -> (Option<u64>, bool, bool) {
+    let size = text.split_whitespace().next().and_then(|size| size.parse::<u64>().ok());
+
+    (size, text.contains("unsized"), text.contains("uninhabited"))
+}
+
+/// Fills in the notable traits of the methods whose return type has some.
+struct NotableTraits<'t>(&'t HashMap<String, Vec<NotableTrait>>);
+
+impl<'a> VisitMut<'a> for NotableTraits<'_> {
+    fn visit_method_mut(&mut self, method: &mut Method<'a>) {
+        if let Some(traits) = method.notable_type.as_ref().and_then(|r#type| self.0.get(r#type)) {
+            method.notable_traits = traits.clone();
+        }
+    }
+}
+
+/// An implementation block's anchor, header and methods.
+type ImplBlock<'a> = (Option<Cow<'a, str>>, Fragment<'a>, Vec<Method<'a>>);
+
+/// Goes through every implementation block of a section, be it a toggle or a lone section.
+///
+/// Returns each block's id and header along with its methods.
+fn impl_blocks<'r, 'a>(content: &'r [&'r HtmlElement<'a>], adapter: &'r Adapter) -> impl Iterator<Item = Result<ImplBlock<'a>, Herr>> + 'r {
+    content
+        .iter()
+        .flat_map(|list| list.inner_elements.iter())
+        .filter(|block| adapter.is_toggle(block) || block.has_class("impl"))
+        .map(|block| {
+            let methods = block
+                .select_with(adapter.methods())
+                .into_iter()
+                .map(|method| Method::parse(method, adapter))
+                .collect::<Result<Vec<Method>, Herr>>()?;
+            Ok((anchor(block), Fragment::Bold(code_header(block, adapter)?), methods))
+        })
+}
+
+/// Builds the pages tests go through out of their main content, and takes their sections apart.
+#[cfg(test)]
+pub(crate) mod test_pages {
+    use crate::{
+        process_html,
+        Page,
+    };
+
+    /// Parses a page of that kind, such as `struct`, out of what its `section#main-content` holds.
+    pub(crate) fn rustdoc_page(kind: &str, main_content: &str) -> Page<'static> {
+        let html = format!("<body class=\"rustdoc {}\"><section id=\"main-content\">{}</section></body>", kind, main_content);
+        process_html(Box::leak(html.into_boxed_str())).unwrap()
+    }
+
+    /// Takes the content of the page's section at that index, panicking unless it's of the given kind.
+    macro_rules! section_content {
+        ($page:expr, $index:expr, $kind:ident) => {
+            match &$page.main_content.0[$index].content {
+                $crate::main_content::SectionContent::$kind(content) => content,
+                content => panic!("No {} in {:?}", stringify!($kind), content),
+            }
+        };
+    }
+    pub(crate) use section_content;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        test_pages::{
+            rustdoc_page,
+            section_content,
+        },
+        Anchored,
+        TraitImplKind,
+        VariantKind,
+        Visibility
+    };
+
+    #[test]
+    fn impl_headers() {
+        let page = rustdoc_page("struct", "\
+            <h2 id=\"synthetic-implementations\" class=\"section-header\">Auto Trait Implementations</h2><div id=\"synthetic-implementations-list\">\
+            <section id=\"impl-Send-for-Rc%3CT,+A%3E\" class=\"impl\"><h3 class=\"code-header\">impl&lt;T, A&gt; !<a class=\"trait\">Send</a> for <a>Rc</a>&lt;T, A&gt;\
+            <div class=\"where\">where\n    T: ?<a>Sized</a>,\n    A: <a>Fn</a>(u8) -&gt; u8,</div></h3></section>\
+            <section id=\"impl-Unpin-for-Rc%3CT,+A%3E\" class=\"impl\"><h3 class=\"code-header\">impl&lt;T: <a>Unpin</a>, A = fn() -&gt; u8&gt; <a>Unpin</a> for <a>Rc</a>&lt;T, A&gt;</h3></section>\
+            </div>");
+        let send = page.main_content.trait_implementation("Send").unwrap();
+        assert_eq!(send.kind, TraitImplKind::Synthetic);
+        assert!(send.negative);
+        assert_eq!((send.trait_path.as_str(), send.self_type.as_str()), ("Send", "Rc<T, A>"));
+        assert_eq!(send.generics, ["T", "A"]);
+        assert_eq!(send.where_clause, ["T: ?Sized", "A: Fn(u8) -> u8"]);
+        let unpin = page.main_content.trait_implementation("Unpin").unwrap();
+        assert!(!unpin.negative);
+        assert_eq!(unpin.generics, ["T: Unpin", "A = fn() -> u8"]);
+        assert!(unpin.where_clause.is_empty());
+    }
+
+    #[test]
+    fn type_layout() {
+        let page = rustdoc_page("enum", "\
+            <h2 id=\"layout\" class=\"section-header\">Layout<a href=\"#layout\" class=\"anchor\">§</a></h2><div class=\"docblock\">\
+            <div class=\"warning\"><p><strong>Note:</strong> Most layout information is <strong>completely unstable</strong>.</p></div>\
+            <p><strong>Size:</strong> 16 bytes</p><p><strong>Size for each variant:</strong></p>\
+            <ul><li><code>Some</code>: 16 bytes</li><li><code>None</code>: 1 byte</li>\
+            <li><code>Never</code>: 0 bytes (<a href=\"https://doc.rust-lang.org/stable/reference/glossary.html#uninhabited\">uninhabited</a>)</li></ul>\
+            </div>");
+        let layout = section_content!(page, 0, Layout);
+        assert_eq!((layout.size, layout.is_unsized, layout.note.as_deref()), (Some(16), false, None));
+        assert_eq!(layout.variants.iter().map(|variant| variant.size).collect::<Vec<Option<u64>>>(), [Some(16), Some(1), Some(0)]);
+        assert!(layout.variants[2].uninhabited);
+        assert_eq!(page.main_content.0[0].to_string(), "Layout\n\nSize: 16 bytes\nSize of Some: 16 bytes\nSize of None: 1 byte\nSize of Never: 0 bytes (uninhabited)");
+    }
+
+    #[test]
+    fn find_anchors() {
+        let page = rustdoc_page("enum", "\
+            <h2 id=\"variants\" class=\"section-header\">Variants</h2><div class=\"variants\"><section id=\"variant.Ok\" class=\"variant\"><h3 class=\"code-header\">Ok</h3></section></div>\
+            <h2 id=\"implementations\" class=\"section-header\">Implementations</h2><div id=\"implementations-list\">\
+            <details class=\"toggle implementors-toggle\"><summary><section id=\"impl-Result\" class=\"impl\"><h3 class=\"code-header\">impl Result</h3></section></summary>\
+            <div class=\"impl-items\"><details class=\"toggle method-toggle\"><summary><section id=\"method.map\" class=\"method\"><h4 class=\"code-header\">pub fn map()</h4></section></summary>\
+            <div class=\"docblock\"><h5 id=\"examples\">Examples</h5></div></details></div></details></div>");
+        assert!(matches!(page.find_anchor("variants"), Some(Anchored::Section(_))));
+        assert!(matches!(page.find_anchor("#variant.Ok"), Some(Anchored::Variant(_))));
+        assert!(matches!(page.find_anchor("impl-Result"), Some(Anchored::Implementation(_))));
+        let Some(Anchored::Method(method)) = page.find_anchor("#method.map") else {
+            panic!("`#method.map` should lead to the method");
+        };
+        assert_eq!(method.id.as_deref(), Some("method.map"));
+        assert!(page.find_anchor("examples").is_none());
+
+        let page = rustdoc_page("trait", "\
+            <h2 id=\"required-methods\" class=\"section-header\">Required Methods</h2><div class=\"methods\">\
+            <details class=\"toggle method-toggle\" open><summary><section id=\"tymethod.next\" class=\"method\"><h4 class=\"code-header\">fn next(&amp;mut self)</h4></section></summary>\
+            <div class=\"docblock\"><p>Advances.</p></div></details></div>\
+            <h2 id=\"provided-methods\" class=\"section-header\">Provided Methods</h2><div class=\"methods\">\
+            <section id=\"method.count\" class=\"method\"><h4 class=\"code-header\">fn count(self) -&gt; usize</h4></section></div>\
+            <h2 id=\"implementors\" class=\"section-header\">Implementors</h2><div id=\"implementors-list\">\
+            <section id=\"impl-Iterator-for-Chars%3C'_%3E\" class=\"impl\"><h3 class=\"code-header\">impl Iterator for Chars&lt;'_&gt;</h3></section></div>");
+        let Some(Anchored::Method(next)) = page.find_anchor("#tymethod.next") else {
+            panic!("`#tymethod.next` should lead to the required method");
+        };
+        assert_eq!(next.description.to_string(), "Advances.");
+        assert!(matches!(page.find_anchor("method.count"), Some(Anchored::Method(_))));
+        assert!(matches!(page.find_anchor("provided-methods"), Some(Anchored::Section(_))));
+        assert!(matches!(page.find_anchor("impl-Iterator-for-Chars%3C'_%3E"), Some(Anchored::TraitImplementation(_))));
+    }
+
+    #[test]
+    fn variant_kinds() {
+        let page = rustdoc_page("enum", "\
+            <h2 id=\"variants\" class=\"section-header\">Variants</h2><div class=\"variants\">\
+            <section id=\"variant.Less\" class=\"variant\"><h3 class=\"code-header\">Less = -1</h3></section><div class=\"docblock\"><p>Less.</p><p>Or smaller.</p></div>\
+            <section id=\"variant.V4\" class=\"variant\"><h3 class=\"code-header\">V4(u32)</h3></section>\
+            <section id=\"variant.Alloc\" class=\"variant\"><h3 class=\"code-header\">Alloc</h3></section><span class=\"item-info\"></span><div class=\"docblock\"><p>Failed.</p></div>\
+            <div class=\"sub-variant\" id=\"variant.Alloc.fields\"><h4>Fields</h4><div class=\"sub-variant-field\">\
+            <span id=\"variant.Alloc.field.size\" class=\"section-header\"><code>size: usize</code></span><div class=\"docblock\"><p>Its size.</p></div></div></div>\
+            </div>");
+        let variants = section_content!(page, 0, Variants);
+        let kinds = variants.iter().map(|variant| variant.kind).collect::<Vec<VariantKind>>();
+        assert_eq!(kinds, [VariantKind::Unit, VariantKind::Tuple, VariantKind::Struct]);
+        assert_eq!(variants[0].discriminant.as_deref(), Some("-1"));
+        assert_eq!(variants[0].description.to_string(), "Less.\n\nOr smaller.");
+        assert_eq!(variants[2].description.to_string(), "Failed.");
+        assert_eq!(variants[2].fields[0].id.as_deref(), Some("variant.Alloc.field.size"));
+        assert!(matches!(page.find_anchor("variant.Alloc.field.size"), Some(Anchored::Field(_))));
+    }
+
+    #[test]
+    fn fields() {
+        let page = rustdoc_page("struct", "\
+            <pre class=\"rust item-decl\"><code>pub struct S {\n    pub start: <a class=\"primitive\">u8</a>,\n    pub(crate) len: usize,\n    hidden: bool,\n}</code></pre>\
+            <h2 id=\"fields\" class=\"fields section-header\">Fields (Non-exhaustive)</h2>\
+            <span id=\"structfield.start\" class=\"structfield section-header\"><code>start: <a class=\"primitive\">u8</a></code></span>\
+            <span class=\"item-info\"><div class=\"stab deprecated\"><span class=\"emoji\">👎</span><span>Deprecated since 1.0.0</span></div></span>\
+            <div class=\"docblock\"><p>Where it starts.</p><pre><code>let s = 0;</code></pre></div>\
+            <span id=\"structfield.len\" class=\"structfield section-header\"><code>len: usize</code></span>\
+            <span id=\"structfield.hidden\" class=\"structfield section-header\">hidden</span>");
+        let fields = section_content!(page, 0, Fields);
+        let names = fields.iter().map(|field| field.to_string()).collect::<Vec<String>>();
+        assert_eq!(names, ["start: u8", "len: usize", "hidden"]);
+        let visibilities = fields.iter().map(|field| field.visibility.clone()).collect::<Vec<Visibility>>();
+        assert_eq!(visibilities, [Visibility::Public, Visibility::Restricted(String::from("pub(crate)")), Visibility::Private]);
+        assert_eq!(fields[0].deprecation.as_ref().map(|notice| notice.len()), Some(1));
+        assert_eq!(fields[0].description.introduction.len(), 2);
+        assert_eq!(
+            super::visibilities("pub struct S<F>\nwhere\n    F: Fn(u8) -> u8,\n{\n    pub f: F,\n}"),
+            [(String::from("f"), Visibility::Public)]
+        );
+        assert_eq!(
+            super::visibilities("pub struct T<F: Fn()>(pub F, u8)\nwhere\n    F: Clone;"),
+            [(String::from("0"), Visibility::Public), (String::from("1"), Visibility::Private)]
+        );
     }
 }
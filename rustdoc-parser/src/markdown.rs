@@ -0,0 +1,515 @@
+use std::borrow::Cow;
+
+use pulldown_cmark::{
+    BrokenLink,
+    CodeBlockKind,
+    CowStr,
+    Event,
+    Options,
+    Parser,
+    Tag,
+    TagEnd,
+};
+
+use crate::helper_types::{
+    description::{
+        push_block,
+        push_item,
+        Description,
+        DescriptionSection,
+    },
+    fragment::Fragment,
+    html_element::HtmlElement,
+    tag::TagKind,
+    tokenizer::{
+        Token,
+        Tokenizer,
+    },
+};
+
+/////////////////////////////////////////////////////////////////////////////
+// Markdown
+/////////////////////////////////////////////////////////////////////////////
+
+/// Parses Markdown docs into the Description rustdoc's HTML for them would give.
+///
+/// Bracketed Rust paths are taken for intra-doc links when backticked or holding a `::` or a `@`.
+pub fn parse(markdown: &str) -> Description<'_> {
+    parse_with_links(markdown, is_intra_doc_link)
+}
+
+/// Parses Markdown docs into a Description, intra-doc links being those `resolves` accepts.
+pub fn parse_with_links(markdown: &str, resolves: impl Fn(&str) -> bool) -> Description<'_> {
+    let mut introduction = Vec::new();
+    let mut sections: Vec<DescriptionSection> = Vec::new();
+    for block in blocks(markdown, resolves) {
+        let fragments = match block {
+            Block::Heading(name) => {
+                sections.push(DescriptionSection {
+                    name: Fragment::Bold(name),
+                    content: Vec::new(),
+                });
+                continue;
+            }
+            Block::Paragraph(fragments) | Block::Other(fragments) => fragments,
+        };
+        match sections.last_mut() {
+            Some(section) => push_block(&mut section.content, fragments),
+            None => push_block(&mut introduction, fragments),
+        }
+    }
+
+    Description {
+        introduction,
+        sections,
+    }
+}
+
+/// Parses the first paragraph of Markdown docs, as shown next to fields and variants.
+pub fn first_paragraph(markdown: &str, resolves: impl Fn(&str) -> bool) -> Vec<Fragment<'_>> {
+    blocks(markdown, resolves)
+        .into_iter()
+        .find_map(|block| match block {
+            Block::Paragraph(fragments) => Some(fragments),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Gathers the Markdown of the `///` or `//!` doc comments found in a piece of source code.
+pub fn doc_comments(source: &str) -> String {
+    source
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start();
+            line.strip_prefix("///")
+                .filter(|_| !line.starts_with("////"))
+                .or(line.strip_prefix("//!"))
+        })
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+/// Whether a link's reference is a Rust path, such as `` `Vec` ``, `Vec::new` or `struct@Foo`.
+///
+/// Bare words, such as `[place]` or the `0` of `v[0]`, are left as text.
+pub fn is_intra_doc_link(reference: &str) -> bool {
+    let backticked = reference.len() > 2 && reference.starts_with('`') && reference.ends_with('`');
+    let path = reference.trim_matches('`');
+    let (disambiguated, path) = path.split_once('@').map(|(_, path)| (true, path)).unwrap_or((false, path));
+    let path = path.trim_end_matches("()").trim_end_matches('!');
+    (backticked || disambiguated || path.contains("::"))
+        && !path.is_empty()
+        && path.split("::").all(|segment| {
+            !segment.is_empty() && segment.chars().all(|character| character.is_alphanumeric() || character == '_')
+        })
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Blocks
+/////////////////////////////////////////////////////////////////////////////
+
+/// One of the blocks found straight in a docblock.
+enum Block<'a> {
+    Heading(Vec<Fragment<'a>>),
+    Paragraph(Vec<Fragment<'a>>),
+    Other(Vec<Fragment<'a>>),
+}
+
+/// What's being gathered until the matching end tag.
+enum Frame<'a> {
+    /// A block straight in the docblock, or one of a list's items.
+    Block(Vec<Fragment<'a>>),
+    Heading(Vec<Fragment<'a>>),
+    CodeBlock { rust: bool, code: String },
+    /// A list straight in the docblock, its items one per line, after their number if it's ordered.
+    List { number: Option<u64>, items: Vec<Fragment<'a>> },
+    Strong(Vec<Fragment<'a>>),
+    /// An inline element, zipped into a single fragment.
+    Inline(Vec<Fragment<'a>>),
+    /// A link, zipped as inline elements are, keeping where it leads.
+    Link { href: Cow<'a, str>, fragments: Vec<Fragment<'a>> },
+    /// A block within another one, zipped into its mere text.
+    Nested(Vec<Fragment<'a>>),
+    /// An HTML block straight in the docblock, parsed as rustdoc's own HTML once whole.
+    HtmlBlock(String),
+    /// An element opened by inline HTML, zipped as inline elements are once closed.
+    Html { name: String, fragments: Vec<Fragment<'a>> },
+}
+
+/// Goes through the Markdown's events, zipping them the way the HTML path zips elements.
+fn blocks(markdown: &str, resolves: impl Fn(&str) -> bool) -> Vec<Block<'_>> {
+    let mut resolve = |link: BrokenLink| {
+        resolves(link.reference.as_ref()).then(|| (CowStr::from(link.reference.trim_matches('`').to_string()), CowStr::from("")))
+    };
+    let parser = Parser::new_with_broken_link_callback(markdown, Options::all(), Some(&mut resolve));
+    let mut blocks = Vec::new();
+    let mut frames: Vec<Frame> = Vec::new();
+    // adjacent text makes a single fragment, as text nodes do
+    let mut merge = false;
+    for event in parser {
+        match event {
+            Event::Start(tag) => {
+                merge = false;
+                let top = frames.is_empty();
+                let in_list = matches!(frames.last(), Some(Frame::List { .. }));
+                frames.push(match tag {
+                    Tag::Paragraph if top => Frame::Block(Vec::new()),
+                    Tag::Heading { .. } if top => Frame::Heading(Vec::new()),
+                    Tag::CodeBlock(kind) if top => Frame::CodeBlock {
+                        rust: match kind {
+                            CodeBlockKind::Indented => true,
+                            CodeBlockKind::Fenced(info) => is_rust(&info),
+                        },
+                        code: String::new(),
+                    },
+                    Tag::List(number) if top => Frame::List {
+                        number,
+                        items: Vec::new(),
+                    },
+                    Tag::Item if in_list => Frame::Block(Vec::new()),
+                    Tag::HtmlBlock if top => Frame::HtmlBlock(String::new()),
+                    Tag::BlockQuote(_) | Tag::Table(_) | Tag::FootnoteDefinition(_) | Tag::DefinitionList if top => {
+                        Frame::Block(Vec::new())
+                    }
+                    Tag::Strong => Frame::Strong(Vec::new()),
+                    Tag::Link { dest_url, .. } if !dest_url.is_empty() => Frame::Link {
+                        href: borrowed(dest_url),
+                        fragments: Vec::new(),
+                    },
+                    Tag::Emphasis | Tag::Strikethrough | Tag::Superscript | Tag::Subscript | Tag::Link { .. } | Tag::Image { .. } => {
+                        Frame::Inline(Vec::new())
+                    }
+                    _ => Frame::Nested(Vec::new()),
+                });
+            }
+            Event::End(end) => {
+                merge = false;
+                // inline HTML left open by the end of its block
+                close_html(&mut frames, None);
+                let Some(frame) = frames.pop() else {
+                    continue;
+                };
+                let fragment = match frame {
+                    Frame::List { items, .. } => {
+                        blocks.push(Block::Other(items));
+                        continue;
+                    }
+                    Frame::Strong(fragments) => Fragment::Bold(fragments),
+                    Frame::Inline(fragments) => zip_inline(fragments),
+                    Frame::Link { href, fragments } => Fragment::Link {
+                        content: Box::new(zip_inline(fragments)),
+                        href,
+                    },
+                    Frame::Nested(fragments) => Fragment::Raw(Cow::Owned(text(&fragments))),
+                    Frame::Html { name, fragments } => zip_html(&name, fragments),
+                    Frame::HtmlBlock(html) => {
+                        let introduction = Description::parse(&HtmlElement::parse_document(&html)).introduction;
+                        if !introduction.is_empty() {
+                            blocks.push(Block::Other(introduction.into_iter().map(Fragment::into_owned).collect()));
+                        }
+                        continue;
+                    }
+                    Frame::CodeBlock { rust, code } => {
+                        let code = match rust {
+                            true => hide_lines(&code),
+                            false => code,
+                        };
+                        let code = code.strip_suffix('\n').unwrap_or(&code);
+                        blocks.push(Block::Other(Vec::from([Fragment::CodeBlock(Box::new(Fragment::Raw(Cow::Owned(String::from(code)))))])));
+                        continue;
+                    }
+                    Frame::Heading(fragments) => {
+                        blocks.push(Block::Heading(fragments));
+                        continue;
+                    }
+                    Frame::Block(fragments) if end == TagEnd::Item => {
+                        if let Some(Frame::List { number, items }) = frames.last_mut() {
+                            push_item(items, *number, fragments);
+                            *number = number.map(|number| number + 1);
+                        }
+                        continue;
+                    }
+                    Frame::Block(fragments) => {
+                        blocks.push(match end {
+                            TagEnd::Paragraph => Block::Paragraph(fragments),
+                            _ => Block::Other(fragments),
+                        });
+                        continue;
+                    }
+                };
+                push(&mut frames, fragment);
+            }
+            Event::Text(text) if matches!(frames.last(), Some(Frame::CodeBlock { .. })) => {
+                if let Some(Frame::CodeBlock { code, .. }) = frames.last_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::Text(text) | Event::InlineMath(text) | Event::DisplayMath(text) => {
+                push_text(&mut frames, borrowed(text), merge);
+                merge = true;
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                push_text(&mut frames, Cow::Borrowed("\n"), merge);
+                merge = true;
+            }
+            Event::Code(code) => {
+                push(&mut frames, Fragment::Code(Box::new(Fragment::Raw(borrowed(code)))));
+                merge = false;
+            }
+            Event::FootnoteReference(label) => {
+                push(&mut frames, Fragment::Raw(borrowed(label)));
+                merge = false;
+            }
+            Event::Html(html) if matches!(frames.last(), Some(Frame::HtmlBlock(_))) => {
+                if let Some(Frame::HtmlBlock(block)) = frames.last_mut() {
+                    block.push_str(&html);
+                }
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                merge = inline_html(&mut frames, &html, merge);
+            }
+            Event::Rule | Event::TaskListMarker(_) => (),
+        }
+    }
+
+    blocks
+}
+
+/// Adds a fragment to the innermost frame, if not in the middle of a list or code block.
+fn push<'a>(frames: &mut [Frame<'a>], fragment: Fragment<'a>) {
+    if let Some(
+        Frame::Block(fragments)
+        | Frame::Heading(fragments)
+        | Frame::Strong(fragments)
+        | Frame::Inline(fragments)
+        | Frame::Link { fragments, .. }
+        | Frame::Nested(fragments)
+        | Frame::Html { fragments, .. },
+    ) = frames.last_mut()
+    {
+        fragments.push(fragment);
+    }
+}
+
+/// Opens or closes the elements of a piece of inline HTML, keeping its text.
+///
+/// Returns whether it ends with text, for the text coming next to merge with.
+fn inline_html(frames: &mut Vec<Frame<'_>>, html: &str, mut merge: bool) -> bool {
+    for token in Tokenizer::new(html) {
+        match token {
+            Token::StartTag { tag, .. } if tag.name == "br" => {
+                push_text(frames, Cow::Borrowed("\n"), merge);
+                merge = true;
+            }
+            Token::StartTag { tag, self_closing: false } if tag.kind == TagKind::Opening => {
+                frames.push(Frame::Html {
+                    name: tag.name.into_owned(),
+                    fragments: Vec::new(),
+                });
+                merge = false;
+            }
+            Token::EndTag(tag) => {
+                close_html(frames, Some(&tag.name));
+                merge = false;
+            }
+            Token::Text(text) => {
+                push_text(frames, Cow::Owned(text.into_owned()), merge);
+                merge = true;
+            }
+            Token::StartTag { .. } | Token::Comment(_) | Token::Doctype(_) => (),
+        }
+    }
+    merge
+}
+
+/// Zips the innermost HTML elements up to the one going by that name, or all of them when unnamed.
+///
+/// An end tag matching none of the open elements is ignored, as browsers do.
+fn close_html(frames: &mut Vec<Frame<'_>>, name: Option<&str>) {
+    let open = frames.iter().rev().take_while(|frame| matches!(frame, Frame::Html { .. }));
+    let count = match name {
+        Some(name) => match open.clone().position(|frame| matches!(frame, Frame::Html { name: opened, .. } if opened == name)) {
+            Some(position) => position + 1,
+            None => return,
+        },
+        None => open.count(),
+    };
+    for _ in 0..count {
+        if let Some(Frame::Html { name, fragments }) = frames.pop() {
+            push(frames, zip_html(&name, fragments));
+        }
+    }
+}
+
+/// Zips an element opened by inline HTML the way HtmlElement::zip_inner zips it.
+fn zip_html<'a>(name: &str, fragments: Vec<Fragment<'a>>) -> Fragment<'a> {
+    match name {
+        "b" | "strong" => Fragment::Bold(fragments),
+        "code" => Fragment::Code(Box::new(Fragment::Raw(Cow::Owned(text(&fragments))))),
+        _ => zip_inline(fragments),
+    }
+}
+
+/// Adds text to the innermost frame, merging it with the text right before if any.
+fn push_text<'a>(frames: &mut [Frame<'a>], text: Cow<'a, str>, merge: bool) {
+    if merge
+        && let Some(
+            Frame::Block(fragments)
+            | Frame::Heading(fragments)
+            | Frame::Strong(fragments)
+            | Frame::Inline(fragments)
+            | Frame::Link { fragments, .. }
+            | Frame::Nested(fragments)
+            | Frame::Html { fragments, .. },
+        ) = frames.last_mut()
+        && let Some(Fragment::Raw(previous)) = fragments.last_mut()
+    {
+        previous.to_mut().push_str(&text);
+        return;
+    }
+    push(frames, Fragment::Raw(text));
+}
+
+/// Borrows the Markdown's text wherever pulldown-cmark does.
+fn borrowed(text: CowStr<'_>) -> Cow<'_, str> {
+    match text {
+        CowStr::Borrowed(text) => Cow::Borrowed(text),
+        text => Cow::Owned(text.into_string()),
+    }
+}
+
+/// Zips an inline element just as HtmlElement::zip_inner does : code if it starts with some, its text otherwise.
+fn zip_inline(fragments: Vec<Fragment<'_>>) -> Fragment<'_> {
+    match fragments.iter().find(|fragment| !matches!(fragment, Fragment::Raw(_))) {
+        Some(code @ Fragment::Code(_)) => code.clone(),
+        _ => Fragment::Raw(Cow::Owned(text(&fragments))),
+    }
+}
+
+/// The plain text of fragments.
+fn text(fragments: &[Fragment]) -> String {
+    fragments
+        .iter()
+        .map(|fragment| match fragment {
+            Fragment::Bold(fragments) => text(fragments),
+            Fragment::Code(fragment)
+            | Fragment::CodeBlock(fragment)
+            | Fragment::Colored(fragment, _)
+            | Fragment::Link { content: fragment, .. } => text(std::slice::from_ref(fragment)),
+            Fragment::Raw(raw) => raw.to_string(),
+        })
+        .collect()
+}
+
+/// Whether a code block is Rust, as those without any language are.
+fn is_rust(info: &str) -> bool {
+    info.split([',', ' ', '\t'])
+        .map(str::trim)
+        .filter(|attribute| !attribute.is_empty())
+        .all(|attribute| {
+            matches!(attribute, "rust" | "ignore" | "should_panic" | "no_run" | "compile_fail" | "test_harness" | "standalone_crate")
+                || attribute.starts_with("edition")
+                || attribute.starts_with("ignore-")
+                || attribute.starts_with('{')
+        })
+}
+
+/// Leaves out the lines rustdoc hides from Rust code blocks, those starting with `# `.
+fn hide_lines(code: &str) -> String {
+    code.split_inclusive('\n')
+        .filter_map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("##") {
+                // an escaped `#`
+                let start = line.len() - trimmed.len();
+                Some(format!("{}{}", &line[..start], &trimmed[1..]))
+            } else if trimmed == "#" || trimmed.trim_end() == "#" || trimmed.starts_with("# ") || trimmed.starts_with("#\t") {
+                None
+            } else {
+                Some(String::from(line))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{
+        doc_comments,
+        first_paragraph,
+        is_intra_doc_link,
+        parse,
+    };
+    use crate::helper_types::{
+        description::Description,
+        fragment::Fragment,
+        html_element::HtmlElement,
+    };
+
+    #[test]
+    fn same_as_html() {
+        let markdown = "Returns the [`Vec`], **sorted**, as *in* [place].\n\n# Examples\n\n```\n# let v = vec![2, 1];\nassert!(v.is_sorted());\n```\n\n- one `item`\n- two";
+        // as rendered by rustdoc, less the highlighting
+        let html = "<p>Returns the <a href=\"struct.Vec.html\"><code>Vec</code></a>, <strong>sorted</strong>, as <em>in</em> [place].</p>\n\
+            <h2 id=\"examples\"><a class=\"doc-anchor\" href=\"#examples\">§</a>Examples</h2>\n\
+            <div class=\"example-wrap\"><pre class=\"rust rust-example-rendered\"><code>assert!(v.is_sorted());</code></pre></div>\n\
+            <ul>\n<li>one <code>item</code></li>\n<li>two</li>\n</ul>";
+        let from_markdown = parse(markdown);
+        let from_html = Description::parse(&HtmlElement::parse_document(html));
+        // intra-doc links leading to their paths rather than pages
+        assert_eq!(from_markdown.to_string(), from_html.to_string());
+        assert_eq!(from_markdown.sections[0].name, from_html.sections[0].name);
+        assert_eq!(from_markdown.sections[0].content, from_html.sections[0].content);
+        assert_eq!(
+            from_markdown.introduction[1],
+            Fragment::Link {
+                content: Box::new(Fragment::Code(Box::new(Fragment::Raw(Cow::from("Vec"))))),
+                href: Cow::from("Vec"),
+            }
+        );
+        assert!(matches!(&from_html.introduction[1], Fragment::Link { href, .. } if href == "struct.Vec.html"));
+        // a bare word in brackets being no path
+        assert_eq!(from_markdown.introduction.last(), Some(&Fragment::Raw(Cow::from(" [place]."))));
+    }
+
+    #[test]
+    fn html() {
+        let markdown = "Uses <code>unsafe</code>, <b>twice</b>,<br>or <kbd>not <em>at all</kbd>.\n\n<div class=\"warning\">Watch out.</div>\n\nLeft <strong>open";
+        let description = parse(markdown);
+        assert_eq!(
+            description.introduction[..5],
+            [
+                Fragment::Raw(Cow::from("Uses ")),
+                Fragment::Code(Box::new(Fragment::Raw(Cow::from("unsafe")))),
+                Fragment::Raw(Cow::from(", ")),
+                Fragment::Bold(Vec::from([Fragment::Raw(Cow::from("twice"))])),
+                Fragment::Raw(Cow::from(",\nor ")),
+            ]
+        );
+        assert_eq!(description.to_string(), "Uses unsafe, twice,\nor not at all.\n\nWatch out.\n\nLeft open");
+        assert_eq!(description.introduction.last(), Some(&Fragment::Bold(Vec::from([Fragment::Raw(Cow::from("open"))]))));
+    }
+
+    #[test]
+    fn doc_comment_links() {
+        let source = "/// A [`Shape`](crate::Shape), or [nothing].\n///\n/// More.\n//// Not docs.\npub struct Area;";
+        assert_eq!(doc_comments(source), "A [`Shape`](crate::Shape), or [nothing].\n\nMore.");
+        assert_eq!(
+            first_paragraph(&doc_comments(source), |_| false),
+            [
+                Fragment::Raw(Cow::from("A ")),
+                Fragment::Link {
+                    content: Box::new(Fragment::Code(Box::new(Fragment::Raw(Cow::from("Shape"))))),
+                    href: Cow::from("crate::Shape"),
+                },
+                Fragment::Raw(Cow::from(", or [nothing].")),
+            ]
+        );
+        assert!(is_intra_doc_link("`Vec::new()`") && is_intra_doc_link("struct@Foo") && !is_intra_doc_link("see below"));
+        assert!(is_intra_doc_link("`Vec`") && is_intra_doc_link("Vec::new") && !is_intra_doc_link("place") && !is_intra_doc_link("0"));
+    }
+}
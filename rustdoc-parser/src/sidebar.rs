@@ -1,50 +1,269 @@
-use crate::helper_types::html_element::HtmlElement;
+use serde_json::Value;
+
+use crate::{
+    helper_types::{
+        html_element::HtmlElement,
+        selector::selector,
+    },
+    version::Adapter,
+};
 
 /////////////////////////////////////////////////////////////////////////////
 // Sidebar
 /////////////////////////////////////////////////////////////////////////////
 
 /// Parses the full Sidebar of a page in a rather neat fashion.
-#[derive(Debug)]
-pub struct Sidebar(pub Vec<SidebarSection>);
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sidebar {
+    /// The page's own table of contents.
+    pub sections: Vec<SidebarSection>,
+    /// The "In std::result" part, leading to the parent module.
+    pub module: Option<ModuleNavigation>,
+}
 
 /// Represents a section of the Sidebar. Methods, Implementations, and such.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SidebarSection {
-    name: String,
-    items: Vec<String>,
+    pub name: String,
+    /// Where the heading links to, such as `#implementations`.
+    pub target: Option<String>,
+    pub items: Vec<SidebarItem>,
+}
+
+/// A single link of the Sidebar.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SidebarItem {
+    pub name: String,
+    /// An anchor within the page, such as `#method.map`, or another page, such as `struct.Iter.html`.
+    pub target: Option<String>,
+    /// Such as `method`, `variant` or `struct`, or the list's own class, such as `trait-implementation`.
+    pub kind: Option<String>,
+}
+
+/// The module a page lies in, along with its other items.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModuleNavigation {
+    /// Such as `std::result`, or just `std` at the root of the crate.
+    pub path: String,
+    /// The module's page, relative to the current one.
+    pub target: Option<String>,
+    /// The module's items, by kind. Recent rustdocs leave those to `sidebar-items.js`.
+    pub sections: Vec<SidebarSection>,
 }
 
 impl Sidebar {
-    /// Builds a new Sidebar.
-    pub fn parse(content: &str) -> Self {
-        Sidebar(
-            content
-                .split_inclusive("</ul>")
-                .map(|s| {
-                    let mut split = s.split_inclusive("</h3>").collect::<Vec<&str>>();
-                    let heading = HtmlElement::parse(&mut split[0]);
-                    let inner_a = &heading.inner_elements[0];
-                    let name = String::from(inner_a.content[0].raw_content());
-                    let items = if split.len() > 1 {
-                        HtmlElement::parse(&mut split[1])
-                            .inner_elements
-                            .iter()
-                            .map(|inner_element| {
-                                if inner_element.inner_elements.is_empty() {
-                                    String::new()
-                                } else {
-                                    String::from(inner_element.inner_elements[0].content[0].un_escape_content().raw_content())
-                                }
-                            })
-                            .collect::<Vec<String>>()
-                    } else {
-                        Vec::new()
-                    };
-
-                    SidebarSection { name, items }
-                })
-                .collect::<Vec<SidebarSection>>(),
-        )
+    /// Builds a new Sidebar out of the sidebar's `<nav>`, each heading followed by its list of items.
+    pub fn parse(nav: &HtmlElement, adapter: &Adapter) -> Self {
+        let module = nav
+            .select_with(selector!(".sidebar-elems h2"))
+            .into_iter()
+            .find_map(|heading| ModuleNavigation::parse(heading));
+        let sections = nav
+            .select_with(adapter.sidebar_sections())
+            .into_iter()
+            .flat_map(|section| section.inner_elements.iter());
+
+        Sidebar {
+            sections: SidebarSection::parse_all(sections, adapter),
+            module,
+        }
+    }
+}
+
+impl SidebarSection {
+    pub fn new(name: String, target: Option<String>, items: Vec<SidebarItem>) -> Self {
+        SidebarSection { name, target, items }
+    }
+
+    /// Goes through a run of elements, each `<h3>` starting a section which the lists after it fill up.
+    fn parse_all<'r, 'a: 'r>(elements: impl Iterator<Item = &'r HtmlElement<'a>>, adapter: &Adapter) -> Vec<Self> {
+        let mut sections: Vec<SidebarSection> = Vec::new();
+        for element in elements {
+            match element.kind.as_ref() {
+                "h3" => sections.push(SidebarSection {
+                    name: String::from(element.text().trim()),
+                    target: link(element).map(String::from),
+                    items: Vec::new(),
+                }),
+                "ul" | "div" => {
+                    if let Some(section) = sections.last_mut() {
+                        let class = element
+                            .attribute("class")
+                            .and_then(|classes| classes.split_ascii_whitespace().find(|class| *class != "block"));
+                        section.items.extend(
+                            element
+                                .select_with(adapter.sidebar_items())
+                                .into_iter()
+                                .filter_map(|item| SidebarItem::parse(item, class)),
+                        );
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        sections
+    }
+}
+
+impl SidebarItem {
+    /// Builds an item out of its `<a>`, leaving out those lacking either.
+    fn parse(item: &HtmlElement, class: Option<&str>) -> Option<Self> {
+        let name = String::from(item.text().trim());
+        let target = item.attribute("href")?;
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(SidebarItem {
+            name,
+            kind: SidebarItem::kind(target).or(class).map(String::from),
+            target: Some(String::from(target)),
+        })
+    }
+
+    /// The kind an anchor or a file name starts with, such as `method` out of `#method.map`.
+    ///
+    /// Module pages being `index.html`s, they are `mod`s.
+    pub fn kind(target: &str) -> Option<&str> {
+        let file = target.rsplit('/').next().unwrap_or(target);
+        if file == "index.html" {
+            return Some("mod");
+        }
+        file.strip_prefix('#')
+            .unwrap_or(file)
+            .split_once('.')
+            .map(|(kind, _)| kind)
+            .filter(|kind| !kind.is_empty() && kind.chars().all(|character| character.is_ascii_lowercase()))
+    }
+}
+
+impl ModuleNavigation {
+    /// Parses the "In std::result" or "In crate std" heading, any other one being left alone.
+    fn parse(heading: &HtmlElement) -> Option<Self> {
+        let text = heading.text();
+        let path = text.trim().strip_prefix("In ")?;
+        let path = path.strip_prefix("crate ").unwrap_or(path);
+
+        Some(ModuleNavigation {
+            path: String::from(path),
+            target: link(heading).map(String::from),
+            sections: Vec::new(),
+        })
+    }
+
+    /// Fills up the module's items out of its `sidebar-items.js`, `window.SIDEBAR_ITEMS = {"struct": ["Iter"], ..};`.
+    ///
+    /// Returns whether the script made sense, the sections being left untouched otherwise.
+    /// DocRoot fills up its pages' navigations by itself, pages parsed on their own being left to the caller.
+    pub fn fill(&mut self, script: &str) -> bool {
+        let items = script
+            .split_once('=')
+            .map(|(_, items)| items.trim().trim_end_matches(';'))
+            .and_then(|items| serde_json::from_str::<Value>(items).ok());
+        let Some(Value::Object(kinds)) = items else {
+            return false;
+        };
+        // the script sits in the module's own directory
+        let directory = self
+            .target
+            .as_deref()
+            .and_then(|target| target.rsplit_once('/'))
+            .map(|(directory, _)| format!("{}/", directory))
+            .unwrap_or_default();
+        self.sections = kinds
+            .iter()
+            .map(|(kind, names)| SidebarSection {
+                name: String::from(kind_heading(kind)),
+                target: None,
+                items: names
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(|name| SidebarItem {
+                        name: String::from(name),
+                        target: Some(match kind.as_str() {
+                            "mod" => format!("{}{}/index.html", directory, name),
+                            kind => format!("{}{}.{}.html", directory, kind, name),
+                        }),
+                        kind: Some(kind.clone()),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        true
+    }
+}
+
+/// The `href` of a heading's link, if it has one.
+fn link<'r>(heading: &'r HtmlElement) -> Option<&'r str> {
+    heading
+        .select_first_with(selector!("a"))
+        .and_then(|link| link.attribute("href"))
+        .filter(|href| !href.is_empty())
+}
+
+/// The heading rustdoc gives to a kind of item.
+fn kind_heading(kind: &str) -> &str {
+    match kind {
+        "attr" => "Attribute Macros",
+        "constant" => "Constants",
+        "derive" => "Derive Macros",
+        "enum" => "Enums",
+        "fn" => "Functions",
+        "keyword" => "Keywords",
+        "macro" => "Macros",
+        "mod" => "Modules",
+        "primitive" => "Primitive Types",
+        "static" => "Statics",
+        "struct" => "Structs",
+        "trait" => "Traits",
+        "traitalias" => "Trait Aliases",
+        "type" => "Type Aliases",
+        "union" => "Unions",
+        kind => kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sidebar;
+    use crate::{
+        helper_types::html_element::HtmlElement,
+        version::Adapter,
+    };
+
+    #[test]
+    fn sidebar_links() {
+        let html = "<nav class=\"sidebar\"><div class=\"sidebar-elems\"><section id=\"rustdoc-toc\">\
+            <h2 class=\"location\"><a href=\"#\">Result</a></h2>\
+            <h3><a href=\"#variants\">Variants</a></h3><ul class=\"block variant\"><li><a href=\"#variant.Err\" title=\"Err\">Err</a></li><li></li></ul>\
+            <h3><a href=\"#trait-implementations\">Trait Implementations</a></h3><ul class=\"block trait-implementation\"><li><a href=\"#impl-Clone-for-Result\">Clone</a></li></ul>\
+            </section><div id=\"rustdoc-modnav\"><h2><a href=\"index.html\">In std::<wbr>result</a></h2></div></div></nav>";
+        let nav = HtmlElement::parse_document(html);
+        let mut sidebar = Sidebar::parse(&nav, &Adapter::default());
+        let items = |sidebar: &Sidebar, index: usize| {
+            sidebar.sections[index]
+                .items
+                .iter()
+                .map(|item| (item.name.clone(), item.target.clone().unwrap_or_default(), item.kind.clone().unwrap_or_default()))
+                .collect::<Vec<(String, String, String)>>()
+        };
+        assert_eq!(sidebar.sections.len(), 2);
+        assert_eq!(sidebar.sections[0].target.as_deref(), Some("#variants"));
+        assert_eq!(items(&sidebar, 0), [(String::from("Err"), String::from("#variant.Err"), String::from("variant"))]);
+        assert_eq!(items(&sidebar, 1)[0].2, "trait-implementation");
+
+        let module = sidebar.module.as_mut().unwrap();
+        assert_eq!((module.path.as_str(), module.target.as_deref()), ("std::result", Some("index.html")));
+        assert!(module.fill("window.SIDEBAR_ITEMS = {\"mod\":[\"inner\"],\"struct\":[\"Iter\"]};"));
+        let targets = module.sections.iter().flat_map(|section| section.items.iter()).filter_map(|item| item.target.as_deref());
+        assert_eq!(targets.collect::<Vec<&str>>(), ["inner/index.html", "struct.Iter.html"]);
     }
 }
@@ -0,0 +1,203 @@
+use std::borrow::Cow;
+
+use crate::{
+    errors::Herr,
+    helper_types::{
+        fragment::{
+            Color,
+            Fragment
+        },
+        html_element::{
+            HtmlElement,
+            Node
+        },
+        selector::selector,
+    },
+};
+
+/////////////////////////////////////////////////////////////////////////////
+// SourcePage
+/////////////////////////////////////////////////////////////////////////////
+
+/// A highlighted source file, such as `src/core/result.rs.html`, which items' `Source` links lead to.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourcePage<'a> {
+    /// Such as `core/result.rs`.
+    pub path: String,
+    /// Each line's code, colored just as rustdoc highlights it, the first line coming first.
+    pub lines: Vec<Vec<Fragment<'a>>>,
+}
+
+impl<'a> SourcePage<'a> {
+    /// Converts a source page into its lines, any other page failing.
+    pub fn parse(html: &'a str) -> Result<Self, Herr> {
+        let document = HtmlElement::parse_document(html);
+        let body = document.select_first_with(selector!("body")).unwrap_or(&document);
+        if !body.has_class("src") && !body.has_class("source") {
+            let kind = match body.has_class("rustdoc") {
+                true => "not a source file",
+                false => "not rustdoc",
+            };
+            return Err(Herr::unsupported_page_kind(kind, body));
+        }
+        let code = body
+            .select_first_with(selector!("pre.rust"))
+            .ok_or_else(|| Herr::missing_section("pre.rust", body))?;
+        let mut lines = Vec::from([Vec::new()]);
+        push_element(code, None, &mut lines);
+        // the file's last newline
+        if lines.last().is_some_and(Vec::is_empty) {
+            lines.pop();
+        }
+
+        Ok(SourcePage {
+            path: body
+                .select_first_with(selector!(".main-heading h1"))
+                .map(|heading| heading.text().split_whitespace().collect::<String>())
+                .unwrap_or_default(),
+            lines,
+        })
+    }
+
+    /// The lines from the first to the last, both included and counting from 1, as a link's `#120-145` tells them.
+    pub fn lines(&self, first: usize, last: usize) -> &[Vec<Fragment<'a>>] {
+        let last = last.min(self.lines.len());
+        let first = first.clamp(1, last.max(1));
+
+        self.lines.get(first - 1..last).unwrap_or_default()
+    }
+
+    /// The line the item starting on the first one ends on, links to functions only telling their signature.
+    ///
+    /// That's the line closing the item's braces, or ending it with a `;`, comments and literals aside.
+    pub fn item_end(&self, first: usize, last: usize) -> usize {
+        let mut depth = 0usize;
+        let mut opened = false;
+        for (number, line) in (first.max(1)..).zip(self.lines.iter().skip(first.saturating_sub(1))) {
+            let code = line
+                .iter()
+                .filter(|fragment| !matches!(fragment, Fragment::Colored(_, Color::Comment | Color::DocComment | Color::Literal)))
+                .map(Fragment::to_string)
+                .collect::<String>();
+            for character in code.chars() {
+                match character {
+                    '{' => {
+                        depth += 1;
+                        opened = true;
+                    }
+                    '}' => depth = depth.saturating_sub(1),
+                    _ => (),
+                }
+                if opened && depth == 0 {
+                    return number.max(last);
+                }
+            }
+            if !opened && number >= last && code.trim_end().ends_with(';') {
+                return number;
+            }
+        }
+
+        last
+    }
+
+    /// Reads the lines a `Source` link points to, such as `(120, 145)` out of `../src/core/result.rs.html#120-145`.
+    pub fn line_range(target: &str) -> Option<(usize, usize)> {
+        let (_, lines) = target.rsplit_once('#')?;
+        let (first, last) = lines.split_once('-').unwrap_or((lines, lines));
+
+        Some((first.parse().ok()?, last.parse().ok()?))
+    }
+}
+
+/// Goes through an element of the code, its text being split into lines and colored after the innermost highlight.
+fn push_element<'a>(element: &HtmlElement<'a>, color: Option<&Color>, lines: &mut Vec<Vec<Fragment<'a>>>) {
+    for child in element.children() {
+        match child {
+            Node::Content(Fragment::Raw(text)) => push_text(text.clone(), color, lines),
+            Node::Content(fragment) => lines.last_mut().into_iter().for_each(|line| line.push(fragment.clone())),
+            Node::Element(element) if is_line_number(element) => (),
+            Node::Element(element) => {
+                let highlight = element
+                    .attribute("class")
+                    .and_then(|classes| classes.split_ascii_whitespace().find_map(highlight));
+                push_element(element, highlight.as_ref().or(color), lines);
+            }
+        }
+    }
+}
+
+/// Adds the text to the current line, each newline starting another one.
+fn push_text<'a>(text: Cow<'a, str>, color: Option<&Color>, lines: &mut Vec<Vec<Fragment<'a>>>) {
+    let pieces = match text {
+        Cow::Borrowed(text) => text.split('\n').map(Cow::Borrowed).collect::<Vec<Cow<str>>>(),
+        Cow::Owned(text) => text.split('\n').map(|piece| Cow::Owned(String::from(piece))).collect(),
+    };
+    for (index, piece) in pieces.into_iter().enumerate() {
+        if index > 0 {
+            lines.push(Vec::new());
+        }
+        if piece.is_empty() {
+            continue;
+        }
+        let fragment = match color {
+            Some(color) => Fragment::Colored(Box::new(Fragment::Raw(piece)), color.clone()),
+            None => Fragment::Raw(piece),
+        };
+        if let Some(line) = lines.last_mut() {
+            line.push(fragment);
+        }
+    }
+}
+
+/// Whether the element is one of the line numbers rustdoc puts at the start of each line, such as `<a href=#1 id=1>1</a>`.
+fn is_line_number(element: &HtmlElement) -> bool {
+    element.kind == "a"
+        && (element.attribute("data-nosnippet").is_some()
+            || element
+                .attribute("id")
+                .is_some_and(|id| id.parse::<usize>().is_ok() && element.attribute("href") == Some(&format!("#{}", id))))
+}
+
+/// The color of one of rustdoc's highlighting classes.
+fn highlight(class: &str) -> Option<Color> {
+    match class {
+        "attr" => Some(Color::Attribute),
+        "bool-val" | "number" | "string" => Some(Color::Literal),
+        "comment" => Some(Color::Comment),
+        "doccomment" => Some(Color::DocComment),
+        "kw" | "kw-2" => Some(Color::Keyword),
+        "lifetime" => Some(Color::Lifetime),
+        "macro" | "macro-nonterminal" => Some(Color::Macro),
+        "prelude-ty" | "prelude-val" => Some(Color::Prelude),
+        "question-mark" => Some(Color::QuestionMark),
+        "self" => Some(Color::SelfValue),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SourcePage;
+    use crate::helper_types::fragment::{
+        Color,
+        Fragment
+    };
+
+    #[test]
+    fn source_lines() {
+        let html = "<body class=\"rustdoc src\"><main><section id=\"main-content\"><div class=\"main-heading\"><h1><div class=\"sub-heading\">core/</div>result.rs</h1></div>\
+            <div class=\"example-wrap\"><pre class=\"rust\"><code><a href=#1 id=1 data-nosnippet>1</a><span class=\"doccomment\">/// Maps.\n\
+            <a href=#2 id=2 data-nosnippet>2</a>/// Twice.\n</span>\
+            <a href=#3 id=3 data-nosnippet>3</a><span class=\"kw\">pub fn </span>map(x: u8) -&gt; u8 { x }\n</code></pre></div></section></main></body>";
+        let source = SourcePage::parse(html).unwrap();
+        assert_eq!(source.path, "core/result.rs");
+        assert_eq!(source.lines.len(), 3);
+        assert_eq!(SourcePage::line_range("../src/core/result.rs.html#2-3"), Some((2, 3)));
+        assert_eq!(source.item_end(3, 3), 3);
+        let lines = source.lines(2, 3);
+        assert_eq!(lines[0], [Fragment::Colored(Box::new(Fragment::Raw("/// Twice.".into())), Color::DocComment)]);
+        assert_eq!(lines[1].iter().map(Fragment::to_string).collect::<String>(), "pub fn map(x: u8) -> u8 { x }");
+        assert!(SourcePage::parse("<body class=\"rustdoc struct\"></body>").is_err());
+    }
+}
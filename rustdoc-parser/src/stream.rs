@@ -0,0 +1,272 @@
+use std::{
+    borrow::Cow,
+    mem,
+};
+
+use crate::{
+    errors::Location,
+    helper_types::{
+        html_element::HtmlElement,
+        tag::Tag,
+        tokenizer::{
+            Token,
+            Tokenizer
+            }
+        },
+    version::{
+        Adapter,
+        RustdocVersion,
+    },
+};
+
+/////////////////////////////////////////////////////////////////////////////
+// Events
+/////////////////////////////////////////////////////////////////////////////
+
+/// What a page is made of, in the order it comes in.
+#[derive(Debug)]
+pub enum Event<'a> {
+    /// The version of rustdoc which generated the page.
+    Version(RustdocVersion),
+    /// The body's start tag alone, its classes telling the kind of page.
+    Body(HtmlElement<'a>),
+    /// The sidebar's `<nav>`, in full.
+    Sidebar(HtmlElement<'a>),
+    /// The main content, holding only what precedes its first section : the declaration and the top docs.
+    Preamble(HtmlElement<'a>),
+    /// The heading starting one of the main content's sections.
+    Heading(HtmlElement<'a>),
+    /// An element of the main content, belonging to the section last started.
+    ///
+    /// Left out when skimming.
+    Content(HtmlElement<'a>),
+    /// The JSON of the `<script id="notable-traits-data">`, each return type's popup keyed by the type.
+    ///
+    /// Told even when skimming, the script lying at the end of the main content.
+    NotableTraits(Cow<'a, str>),
+    /// The end of the main content, as a byte offset.
+    MainEnd(usize),
+}
+
+/// Where the tokenizer stands within the page.
+enum Place<'a> {
+    /// Outside of the main content, where tokens are gone through one by one.
+    Outside,
+    /// Within the main content, before its first section.
+    Preamble(HtmlElement<'a>),
+    /// Within the main content's sections, the main content's name telling where they end.
+    Sections(Cow<'a, str>),
+}
+
+/// Oldest markup, which pages may follow for as long as they don't tell their version.
+const OLDEST: RustdocVersion = RustdocVersion::new(1, 0, 0);
+
+/////////////////////////////////////////////////////////////////////////////
+// PageStream
+/////////////////////////////////////////////////////////////////////////////
+
+/// Goes through a page in a single forward pass of the Tokenizer, turning it into Events.
+///
+/// Only the sidebar and the main content's elements are built up into HtmlElements, each one on its own.
+pub struct PageStream<'a> {
+    tokenizer: Tokenizer<'a>,
+    /// Whether the sections' content is gone past rather than built up.
+    skim: bool,
+    version: Option<RustdocVersion>,
+    place: Place<'a>,
+    /// An Event which came along with another one.
+    pending: Option<Event<'a>>,
+}
+
+impl<'a> PageStream<'a> {
+    pub fn new(html: &'a str) -> Self {
+        PageStream {
+            tokenizer: Tokenizer::new(html),
+            skim: false,
+            version: None,
+            place: Place::Outside,
+            pending: None,
+        }
+    }
+
+    /// Builds a new PageStream which only tells where sections start, their headings aside.
+    pub fn skimming(html: &'a str) -> Self {
+        PageStream {
+            skim: true,
+            ..PageStream::new(html)
+        }
+    }
+
+    /// Handles a start tag found outside of the main content.
+    fn start(&mut self, tag: Tag<'a>, self_closing: bool, location: Location) -> Option<Event<'a>> {
+        let element = HtmlElement::located(tag, location);
+        if is_notable_traits(&element) {
+            return Some(self.notable_traits(element, self_closing));
+        }
+        match element.kind.as_ref() {
+            "body" => return Some(Event::Body(element)),
+            "nav" if element.has_class("sidebar") => {
+                return Some(Event::Sidebar(HtmlElement::grow(&mut self.tokenizer, element, self_closing)));
+            }
+            _ => (),
+        }
+        let id = element.attribute("id");
+        if (element.kind == "meta" && element.attribute("name") == Some("rustdoc-vars")) || id == Some("rustdoc-vars") {
+            let version = element
+                .attribute("data-rustdoc-version")
+                .and_then(RustdocVersion::parse)?;
+            self.version = Some(version);
+            return Some(Event::Version(version));
+        }
+        if id.is_some_and(|id| self.either(|adapter| adapter.main_content_id() == id)) && !self_closing {
+            self.place = Place::Preamble(element);
+        }
+
+        None
+    }
+
+    /// Handles an element of the main content, whose start tag was just read.
+    fn main_element(&mut self, element: HtmlElement<'a>, self_closing: bool) -> Option<Event<'a>> {
+        if is_notable_traits(&element) {
+            return Some(self.notable_traits(element, self_closing));
+        }
+        let heading = self.either(|adapter| adapter.is_section_heading(&element));
+        if self.skim && !heading && matches!(self.place, Place::Sections(_)) {
+            HtmlElement::skip(&mut self.tokenizer, element, self_closing);
+            return None;
+        }
+        let element = HtmlElement::grow(&mut self.tokenizer, element, self_closing);
+        match &mut self.place {
+            Place::Preamble(main) if !heading => {
+                main.push_element(element);
+                None
+            }
+            Place::Preamble(_) => {
+                let Place::Preamble(main) = mem::replace(&mut self.place, Place::Outside) else {
+                    return None;
+                };
+                self.place = Place::Sections(main.kind.clone());
+                self.pending = Some(Event::Heading(element));
+                Some(Event::Preamble(main))
+            }
+            _ if heading => Some(Event::Heading(element)),
+            _ => Some(Event::Content(element)),
+        }
+    }
+
+    /// Reads the notable traits' script, whose start tag was just read, its text coming as is.
+    fn notable_traits(&mut self, script: HtmlElement<'a>, self_closing: bool) -> Event<'a> {
+        Event::NotableTraits(HtmlElement::grow(&mut self.tokenizer, script, self_closing).text())
+    }
+
+    /// Handles an end tag starting at the offset, which may close the main content.
+    fn end(&mut self, tag: Tag<'a>, offset: usize) -> Option<Event<'a>> {
+        let closes = match &self.place {
+            Place::Outside => false,
+            Place::Preamble(main) => tag.name == main.kind,
+            Place::Sections(kind) => tag.name == *kind,
+        };
+        match closes {
+            true => self.leave(offset),
+            false => None,
+        }
+    }
+
+    /// Leaves the main content ending at the offset, with the preamble if no section came after it.
+    fn leave(&mut self, offset: usize) -> Option<Event<'a>> {
+        match mem::replace(&mut self.place, Place::Outside) {
+            Place::Outside => None,
+            Place::Preamble(main) => {
+                self.pending = Some(Event::MainEnd(offset));
+                Some(Event::Preamble(main))
+            }
+            Place::Sections(_) => Some(Event::MainEnd(offset)),
+        }
+    }
+
+    /// Whether the markup of the page's version fits, either markup doing so while the version is unknown.
+    fn either(&self, fits: impl Fn(&Adapter) -> bool) -> bool {
+        fits(&Adapter::new(self.version)) || (self.version.is_none() && fits(&Adapter::new(Some(OLDEST))))
+    }
+}
+
+/// Whether the element is the script holding the popups of the notable traits.
+fn is_notable_traits(element: &HtmlElement) -> bool {
+    element.kind == "script" && element.attribute("id") == Some("notable-traits-data")
+}
+
+impl<'a> Iterator for PageStream<'a> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Event<'a>> {
+        if let Some(event) = self.pending.take() {
+            return Some(event);
+        }
+        loop {
+            let location = self.tokenizer.location();
+            let Some(token) = self.tokenizer.next() else {
+                return self.leave(self.tokenizer.offset());
+            };
+            let event = match token {
+                Token::StartTag { tag, self_closing } if matches!(self.place, Place::Outside) => {
+                    self.start(tag, self_closing, location)
+                }
+                Token::StartTag { tag, self_closing } => {
+                    self.main_element(HtmlElement::located(tag, location), self_closing)
+                }
+                Token::Text(text) => {
+                    if let Place::Preamble(main) = &mut self.place {
+                        main.push_content(text);
+                    }
+                    None
+                }
+                Token::EndTag(tag) => self.end(tag, location.offset),
+                Token::Comment(_) | Token::Doctype(_) => None,
+            };
+            if event.is_some() {
+                return event;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Event,
+        PageStream
+    };
+
+    #[test]
+    fn stream_events() {
+        let page = "<html><head><meta name=\"rustdoc-vars\" data-rustdoc-version=\"1.95.0\"></head>\
+            <body class=\"rustdoc struct\"><nav class=\"sidebar\"><h2>S</h2></nav>\
+            <section id=\"main-content\"><pre class=\"item-decl\"><code>struct S;</code></pre><p>intro\
+            <h2 class=\"section-header\">Implementations</h2><div id=\"implementations-list\"></div>\
+            <script type=\"text/json\" id=\"notable-traits-data\">{\"Iter\":\"<h3>&lt;/script&gt;</h3>\"}</script>\
+            </section><footer></footer></body></html>";
+        let events: Vec<String> = PageStream::new(page)
+            .map(|event| match event {
+                Event::Version(version) => version.to_string(),
+                Event::Body(body) => body.to_html(),
+                Event::Sidebar(nav) | Event::Heading(nav) | Event::Content(nav) => nav.to_html(),
+                Event::Preamble(main) => format!("{} elements", main.inner_elements.len()),
+                Event::NotableTraits(data) => data.into_owned(),
+                Event::MainEnd(offset) => page[offset..].chars().take(10).collect(),
+            })
+            .collect();
+        assert_eq!(
+            events,
+            [
+                "1.95.0",
+                "<body class=\"rustdoc struct\"></body>",
+                "<nav class=\"sidebar\"><h2>S</h2></nav>",
+                "2 elements",
+                "<h2 class=\"section-header\">Implementations</h2>",
+                "<div id=\"implementations-list\"></div>",
+                "{\"Iter\":\"<h3>&lt;/script&gt;</h3>\"}",
+                "</section>",
+            ]
+        );
+    }
+}
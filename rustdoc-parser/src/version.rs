@@ -0,0 +1,216 @@
+use std::fmt::{
+    Display,
+    Formatter,
+    Result
+};
+
+use crate::helper_types::{
+    html_element::HtmlElement,
+    selector::{
+        selector,
+        Selector,
+    },
+};
+
+/////////////////////////////////////////////////////////////////////////////
+// Version
+/////////////////////////////////////////////////////////////////////////////
+
+/// The version of rustdoc a page was generated with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RustdocVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl RustdocVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        RustdocVersion { major, minor, patch }
+    }
+
+    /// Parses a version such as `1.95.0 (59807616e 2026-04-14)` or `1.97.0-nightly (e50aa6fba 2026-05-19)`.
+    pub fn parse(version: &str) -> Option<Self> {
+        let number = version.split_whitespace().next()?;
+        let number = number.split(['-', '+']).next()?;
+        let mut numbers = number.split('.').map(|number| number.parse::<u32>());
+        let major = numbers.next()?.ok()?;
+        let minor = numbers.next()?.ok()?;
+        let patch = match numbers.next() {
+            Some(patch) => patch.ok()?,
+            None => 0,
+        };
+
+        Some(RustdocVersion::new(major, minor, patch))
+    }
+
+    /// Reads the version off the `rustdoc-vars`, be it the `<meta>` of the head, or the `<div>` of older pages.
+    pub fn detect(document: &HtmlElement) -> Option<Self> {
+        document
+            .select_with(selector!("meta[name=rustdoc-vars], #rustdoc-vars"))
+            .into_iter()
+            .find_map(|vars| vars.attribute("data-rustdoc-version"))
+            .and_then(RustdocVersion::parse)
+    }
+}
+
+impl Display for RustdocVersion {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Markup changes
+/////////////////////////////////////////////////////////////////////////////
+
+/// Item headers are wrapped in a `.code-header`, rather than being a heading's lone `<code>`.
+const CODE_HEADER: RustdocVersion = RustdocVersion::new(1, 57, 0);
+/// The main content is `section#main-content`, rather than `section#main`.
+const MAIN_CONTENT: RustdocVersion = RustdocVersion::new(1, 59, 0);
+/// Toggles are `details.toggle`, rather than `details.rustdoc-toggle`.
+const TOGGLE: RustdocVersion = RustdocVersion::new(1, 63, 0);
+/// Section headings are `h2.section-header`, rather than `h2.small-section-header`,
+/// and the sidebar lists its items in `ul.block`s, rather than in `div.sidebar-links`.
+const SECTION_HEADER: RustdocVersion = RustdocVersion::new(1, 64, 0);
+/// Variants are `section.variant`s within a `div.variants`, rather than headings right in the main content.
+const VARIANTS: RustdocVersion = RustdocVersion::new(1, 68, 0);
+
+/////////////////////////////////////////////////////////////////////////////
+// Adapter
+/////////////////////////////////////////////////////////////////////////////
+
+/// Tells how the pages of a given rustdoc version are laid out.
+///
+/// Pages of unknown versions are taken to follow the latest markup.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Adapter {
+    pub version: Option<RustdocVersion>,
+}
+
+impl Adapter {
+    pub fn new(version: Option<RustdocVersion>) -> Self {
+        Adapter { version }
+    }
+
+    /// Builds the Adapter fitting the document's rustdoc version.
+    pub fn detect(document: &HtmlElement) -> Self {
+        Adapter::new(RustdocVersion::detect(document))
+    }
+
+    /// Whether the page was generated by a rustdoc at least as recent as the given version.
+    fn since(&self, version: RustdocVersion) -> bool {
+        self.version.is_none_or(|current| current >= version)
+    }
+
+    /// Finds the page's main content.
+    pub fn main_content<'r, 'a>(&self, document: &'r HtmlElement<'a>) -> Option<&'r HtmlElement<'a>> {
+        document.find_by_id(self.main_content_id())
+    }
+
+    /// The id of the page's main content.
+    pub fn main_content_id(&self) -> &'static str {
+        match self.since(MAIN_CONTENT) {
+            true => "main-content",
+            false => "main",
+        }
+    }
+
+    /// Whether the element is the heading of one of the main content's sections.
+    pub fn is_section_heading(&self, element: &HtmlElement) -> bool {
+        element.kind == "h2"
+            && match self.since(SECTION_HEADER) {
+                true => element.has_class("section-header"),
+                false => element.has_class("small-section-header"),
+            }
+    }
+
+    /// Names a section the way the latest rustdoc does.
+    ///
+    /// "Object Safety" became "Dyn Compatibility" with 1.83, and non-exhaustive items' "Fields (Non-exhaustive)" are mere Fields.
+    pub fn section_name<'a>(&self, name: &'a str) -> &'a str {
+        match name.trim_end_matches(" (Non-exhaustive)") {
+            "Object Safety" => "Dyn Compatibility",
+            name => name,
+        }
+    }
+
+    /// Selects an item's header, holding its signature.
+    pub fn code_header(&self) -> &'static Selector {
+        match self.since(CODE_HEADER) {
+            true => selector!(".code-header"),
+            false => selector!("code"),
+        }
+    }
+
+    /// Whether the element is a toggle, which hides an item's details.
+    pub fn is_toggle(&self, element: &HtmlElement) -> bool {
+        element.kind == "details"
+            && match self.since(TOGGLE) {
+                true => element.has_class("toggle"),
+                false => element.has_class("rustdoc-toggle"),
+            }
+    }
+
+    /// Selects the methods of an implementation block.
+    pub fn methods(&self) -> &'static Selector {
+        match self.since(CODE_HEADER) {
+            true => selector!(".impl-items > details, .impl-items > section"),
+            false => selector!(".impl-items > details, .impl-items > .method"),
+        }
+    }
+
+    /// Lays out the variants and their docblocks side by side.
+    pub fn variants<'r, 'a>(&self, content: &[&'r HtmlElement<'a>]) -> Vec<&'r HtmlElement<'a>> {
+        match self.since(VARIANTS) {
+            true => content
+                .iter()
+                .flat_map(|container| container.inner_elements.iter())
+                .collect(),
+            false => content.to_vec(),
+        }
+    }
+
+    /// Selects the sidebar's sections, each a heading and its items.
+    pub fn sidebar_sections(&self) -> &'static Selector {
+        match self.since(SECTION_HEADER) {
+            true => selector!(".sidebar-elems section"),
+            false => selector!(".sidebar-elems .block"),
+        }
+    }
+
+    /// Selects the links of a sidebar section's items.
+    pub fn sidebar_items(&self) -> &'static Selector {
+        match self.since(SECTION_HEADER) {
+            true => selector!("li > a"),
+            false => selector!("a"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Adapter,
+        RustdocVersion,
+    };
+    use crate::helper_types::{
+        html_element::HtmlElement,
+        selector::Selector,
+    };
+
+    #[test]
+    fn detect_version() {
+        assert_eq!(RustdocVersion::parse("1.95.0 (59807616e 2026-04-14)"), Some(RustdocVersion::new(1, 95, 0)));
+        assert_eq!(RustdocVersion::parse("1.97.0-nightly (e50aa6fba 2026-05-19)"), Some(RustdocVersion::new(1, 97, 0)));
+        assert_eq!(RustdocVersion::parse("nightly"), None);
+
+        let current = HtmlElement::parse_document("<head><meta name=\"rustdoc-vars\" data-rustdoc-version=\"1.83.0 (90b35a623 2024-11-26)\"></head>");
+        let legacy = HtmlElement::parse_document("<body><div id=\"rustdoc-vars\" data-rustdoc-version=\"1.60.0\"></div></body>");
+        assert_eq!(Adapter::detect(&current).version, Some(RustdocVersion::new(1, 83, 0)));
+        assert_eq!(Adapter::detect(&legacy).section_name("Object Safety"), "Dyn Compatibility");
+        assert_eq!(Adapter::detect(&legacy).code_header(), &Selector::parse(".code-header").unwrap());
+        assert_eq!(Adapter::detect(&HtmlElement::parse_document("")).version, None);
+    }
+}
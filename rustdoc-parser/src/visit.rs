@@ -0,0 +1,328 @@
+use crate::{
+    helper_types::{
+        description::{
+            Description,
+            DescriptionSection
+        },
+        fragment::Fragment,
+        method::{
+            Method,
+            NotableTrait
+        },
+        scraped_example::ScrapedExample
+    },
+    main_content::{
+        Field,
+        Implementation,
+        MainContent,
+        RequiredAssociatedType,
+        Section,
+        SectionContent,
+        TraitImplementation,
+        TypeLayout,
+        Variant
+    },
+    sidebar::{
+        ModuleNavigation,
+        Sidebar,
+        SidebarItem,
+        SidebarSection
+    },
+    Page,
+};
+
+/// Walks a Page by reference, the way syn's visitors do.
+///
+/// Each method defaults to the function of the same name, which goes on to the node's children :
+/// overriding a method and calling that function from it keeps the walk going.
+///
+/// `'r` is how long the nodes are borrowed for, and `'a` how long their text lives.
+pub trait Visit<'r, 'a: 'r> {
+    fn visit_page(&mut self, page: &'r Page<'a>) {
+        visit_page(self, page)
+    }
+
+    fn visit_sidebar(&mut self, sidebar: &'r Sidebar) {
+        visit_sidebar(self, sidebar)
+    }
+
+    fn visit_sidebar_section(&mut self, section: &'r SidebarSection) {
+        visit_sidebar_section(self, section)
+    }
+
+    fn visit_sidebar_item(&mut self, item: &'r SidebarItem) {}
+
+    fn visit_module_navigation(&mut self, module: &'r ModuleNavigation) {
+        visit_module_navigation(self, module)
+    }
+
+    fn visit_main_content(&mut self, main_content: &'r MainContent<'a>) {
+        visit_main_content(self, main_content)
+    }
+
+    fn visit_section(&mut self, section: &'r Section<'a>) {
+        visit_section(self, section)
+    }
+
+    fn visit_section_content(&mut self, content: &'r SectionContent<'a>) {
+        visit_section_content(self, content)
+    }
+
+    fn visit_field(&mut self, field: &'r Field<'a>) {
+        visit_field(self, field)
+    }
+
+    fn visit_implementation(&mut self, implementation: &'r Implementation<'a>) {
+        visit_implementation(self, implementation)
+    }
+
+    fn visit_required_associated_type(&mut self, r#type: &'r RequiredAssociatedType<'a>) {
+        visit_required_associated_type(self, r#type)
+    }
+
+    fn visit_trait_implementation(&mut self, implementation: &'r TraitImplementation<'a>) {
+        visit_trait_implementation(self, implementation)
+    }
+
+    fn visit_variant(&mut self, variant: &'r Variant<'a>) {
+        visit_variant(self, variant)
+    }
+
+    fn visit_method(&mut self, method: &'r Method<'a>) {
+        visit_method(self, method)
+    }
+
+    fn visit_notable_trait(&mut self, notable_trait: &'r NotableTrait) {
+        visit_notable_trait(self, notable_trait)
+    }
+
+    fn visit_scraped_example(&mut self, example: &'r ScrapedExample) {
+        visit_scraped_example(self, example)
+    }
+
+    fn visit_layout(&mut self, layout: &'r TypeLayout) {
+        visit_layout(self, layout)
+    }
+
+    fn visit_description(&mut self, description: &'r Description<'a>) {
+        visit_description(self, description)
+    }
+
+    fn visit_description_section(&mut self, section: &'r DescriptionSection<'a>) {
+        visit_description_section(self, section)
+    }
+
+    fn visit_fragment(&mut self, fragment: &'r Fragment<'a>) {
+        visit_fragment(self, fragment)
+    }
+
+    /// The text of a `Fragment::Raw`, the very leaves of the Page.
+    fn visit_text(&mut self, text: &'r str) {}
+}
+
+pub fn visit_page<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, page: &'r Page<'a>) {
+    visitor.visit_sidebar(&page.sidebar);
+    visit_fragments(visitor, &page.entry);
+    visitor.visit_description(&page.introduction);
+    visitor.visit_main_content(&page.main_content);
+    for example in &page.examples {
+        visitor.visit_scraped_example(example);
+    }
+}
+
+pub fn visit_sidebar<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, sidebar: &'r Sidebar) {
+    for section in &sidebar.sections {
+        visitor.visit_sidebar_section(section);
+    }
+    if let Some(module) = &sidebar.module {
+        visitor.visit_module_navigation(module);
+    }
+}
+
+pub fn visit_sidebar_section<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, section: &'r SidebarSection) {
+    for item in &section.items {
+        visitor.visit_sidebar_item(item);
+    }
+}
+
+pub fn visit_module_navigation<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, module: &'r ModuleNavigation) {
+    for section in &module.sections {
+        visitor.visit_sidebar_section(section);
+    }
+}
+
+pub fn visit_main_content<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, main_content: &'r MainContent<'a>) {
+    for section in &main_content.0 {
+        visitor.visit_section(section);
+    }
+}
+
+pub fn visit_section<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, section: &'r Section<'a>) {
+    visitor.visit_fragment(&section.name);
+    visitor.visit_section_content(&section.content);
+}
+
+pub fn visit_section_content<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, content: &'r SectionContent<'a>) {
+    match content {
+        SectionContent::Dummy => (),
+        SectionContent::Layout(layout) => visitor.visit_layout(layout),
+        SectionContent::Methods(methods) => methods.iter().for_each(|method| visitor.visit_method(method)),
+        SectionContent::Fields(fields) => fields.iter().for_each(|field| visitor.visit_field(field)),
+        SectionContent::Implementations(implementations) => implementations
+            .iter()
+            .for_each(|implementation| visitor.visit_implementation(implementation)),
+        SectionContent::ObjectSafety(fragments) => visit_fragments(visitor, fragments),
+        SectionContent::RequiredAssociatedTypes(types) => types
+            .iter()
+            .for_each(|r#type| visitor.visit_required_associated_type(r#type)),
+        SectionContent::TraitImplementations(implementations) => implementations
+            .iter()
+            .for_each(|implementation| visitor.visit_trait_implementation(implementation)),
+        SectionContent::Variants(variants) => variants.iter().for_each(|variant| visitor.visit_variant(variant)),
+    }
+}
+
+pub fn visit_field<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, field: &'r Field<'a>) {
+    visit_fragments(visitor, &field.r#type);
+    if let Some(deprecation) = &field.deprecation {
+        visit_fragments(visitor, deprecation);
+    }
+    visitor.visit_description(&field.description);
+}
+
+pub fn visit_implementation<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, implementation: &'r Implementation<'a>) {
+    visitor.visit_fragment(&implementation.inherent_impl);
+    for method in &implementation.methods {
+        visitor.visit_method(method);
+    }
+}
+
+pub fn visit_required_associated_type<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, r#type: &'r RequiredAssociatedType<'a>) {
+    visitor.visit_fragment(&r#type.name);
+    visitor.visit_fragment(&r#type.description);
+}
+
+pub fn visit_trait_implementation<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, implementation: &'r TraitImplementation<'a>) {
+    visitor.visit_fragment(&implementation.trait_impl);
+    for method in &implementation.methods {
+        visitor.visit_method(method);
+    }
+}
+
+pub fn visit_variant<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, variant: &'r Variant<'a>) {
+    visitor.visit_fragment(&variant.name);
+    for field in &variant.fields {
+        visitor.visit_field(field);
+    }
+    visitor.visit_description(&variant.description);
+}
+
+pub fn visit_method<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, method: &'r Method<'a>) {
+    visit_fragments(visitor, &method.signature);
+    for notable_trait in &method.notable_traits {
+        visitor.visit_notable_trait(notable_trait);
+    }
+    visitor.visit_description(&method.description);
+    for example in &method.examples {
+        visitor.visit_scraped_example(example);
+    }
+}
+
+/// Notable traits are mere paths, with nothing below them to walk.
+pub fn visit_notable_trait<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, notable_trait: &'r NotableTrait) {}
+
+/// Scraped examples keep their code as plain text rather than Fragments.
+pub fn visit_scraped_example<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, example: &'r ScrapedExample) {}
+
+/// Layouts are sizes alone, so the walk ends with them.
+pub fn visit_layout<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, layout: &'r TypeLayout) {}
+
+pub fn visit_description<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, description: &'r Description<'a>) {
+    visit_fragments(visitor, &description.introduction);
+    for section in &description.sections {
+        visitor.visit_description_section(section);
+    }
+}
+
+pub fn visit_description_section<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, section: &'r DescriptionSection<'a>) {
+    visitor.visit_fragment(&section.name);
+    visit_fragments(visitor, &section.content);
+}
+
+pub fn visit_fragment<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, fragment: &'r Fragment<'a>) {
+    match fragment {
+        Fragment::Bold(fragments) => visit_fragments(visitor, fragments),
+        Fragment::Code(fragment)
+        | Fragment::CodeBlock(fragment)
+        | Fragment::Colored(fragment, _)
+        | Fragment::Link { content: fragment, .. } => visitor.visit_fragment(fragment),
+        Fragment::Raw(text) => visitor.visit_text(text),
+    }
+}
+
+fn visit_fragments<'r, 'a: 'r, V: Visit<'r, 'a> + ?Sized>(visitor: &mut V, fragments: &'r [Fragment<'a>]) {
+    for fragment in fragments {
+        visitor.visit_fragment(fragment);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        visit_fragment,
+        Visit
+    };
+    use crate::{
+        helper_types::fragment::Fragment,
+        main_content::{
+            test_pages::rustdoc_page,
+            TypeLayout,
+        },
+    };
+
+    /// Gathers the code of a page, leaving the prose out.
+    #[derive(Default)]
+    struct Code<'r>(Vec<&'r str>);
+
+    impl<'r, 'a: 'r> Visit<'r, 'a> for Code<'r> {
+        fn visit_fragment(&mut self, fragment: &'r Fragment<'a>) {
+            match fragment {
+                Fragment::Code(code) => {
+                    if let Fragment::Raw(text) = code.as_ref() {
+                        self.0.push(text);
+                    }
+                }
+                fragment => visit_fragment(self, fragment),
+            }
+        }
+    }
+
+    #[test]
+    fn visit_page() {
+        let page = rustdoc_page("struct", "\
+            <details class=\"toggle top-doc\" open><summary></summary><div class=\"docblock\"><p>A <code>S</code>, <strong>as in <code>Self</code></strong>.</p></div></details>\
+            <h2 class=\"section-header\">Fields</h2><span class=\"structfield\"><code>x: u8</code></span><div class=\"docblock\"><p>With a <code>u8</code>.</p></div>");
+        let mut code = Code::default();
+        code.visit_page(&page);
+        assert_eq!(code.0, ["S", "Self", "u8"]);
+    }
+
+    /// Gathers the sizes of a page's layouts.
+    #[derive(Default)]
+    struct Sizes(Vec<Option<u64>>);
+
+    impl<'r, 'a: 'r> Visit<'r, 'a> for Sizes {
+        fn visit_layout(&mut self, layout: &'r TypeLayout) {
+            self.0.push(layout.size);
+        }
+    }
+
+    #[test]
+    fn visit_layout() {
+        let page = rustdoc_page("struct", "\
+            <h2 id=\"layout\" class=\"section-header\">Layout</h2><div class=\"docblock\"><p><strong>Size:</strong> 16 bytes</p></div>");
+        let mut sizes = Sizes::default();
+        sizes.visit_page(&page);
+        assert_eq!(sizes.0, [Some(16)]);
+    }
+}
@@ -0,0 +1,305 @@
+use std::borrow::Cow;
+
+use crate::{
+    helper_types::{
+        description::{
+            Description,
+            DescriptionSection
+        },
+        fragment::Fragment,
+        method::{
+            Method,
+            NotableTrait
+        },
+        scraped_example::ScrapedExample
+    },
+    main_content::{
+        Field,
+        Implementation,
+        MainContent,
+        RequiredAssociatedType,
+        Section,
+        SectionContent,
+        TraitImplementation,
+        TypeLayout,
+        Variant
+    },
+    sidebar::{
+        ModuleNavigation,
+        Sidebar,
+        SidebarItem,
+        SidebarSection
+    },
+    Page,
+};
+
+/// Walks a Page by mutable reference, so that nodes can be changed along the way.
+///
+/// Each method defaults to the function of the same name, which goes on to the node's children :
+/// overriding a method and calling that function from it keeps the walk going.
+///
+/// `'a` is how long the text lives.
+pub trait VisitMut<'a> {
+    fn visit_page_mut(&mut self, page: &mut Page<'a>) {
+        visit_page_mut(self, page)
+    }
+
+    fn visit_sidebar_mut(&mut self, sidebar: &mut Sidebar) {
+        visit_sidebar_mut(self, sidebar)
+    }
+
+    fn visit_sidebar_section_mut(&mut self, section: &mut SidebarSection) {
+        visit_sidebar_section_mut(self, section)
+    }
+
+    fn visit_sidebar_item_mut(&mut self, item: &mut SidebarItem) {}
+
+    fn visit_module_navigation_mut(&mut self, module: &mut ModuleNavigation) {
+        visit_module_navigation_mut(self, module)
+    }
+
+    fn visit_main_content_mut(&mut self, main_content: &mut MainContent<'a>) {
+        visit_main_content_mut(self, main_content)
+    }
+
+    fn visit_section_mut(&mut self, section: &mut Section<'a>) {
+        visit_section_mut(self, section)
+    }
+
+    fn visit_section_content_mut(&mut self, content: &mut SectionContent<'a>) {
+        visit_section_content_mut(self, content)
+    }
+
+    fn visit_field_mut(&mut self, field: &mut Field<'a>) {
+        visit_field_mut(self, field)
+    }
+
+    fn visit_implementation_mut(&mut self, implementation: &mut Implementation<'a>) {
+        visit_implementation_mut(self, implementation)
+    }
+
+    fn visit_required_associated_type_mut(&mut self, r#type: &mut RequiredAssociatedType<'a>) {
+        visit_required_associated_type_mut(self, r#type)
+    }
+
+    fn visit_trait_implementation_mut(&mut self, implementation: &mut TraitImplementation<'a>) {
+        visit_trait_implementation_mut(self, implementation)
+    }
+
+    fn visit_variant_mut(&mut self, variant: &mut Variant<'a>) {
+        visit_variant_mut(self, variant)
+    }
+
+    fn visit_method_mut(&mut self, method: &mut Method<'a>) {
+        visit_method_mut(self, method)
+    }
+
+    fn visit_notable_trait_mut(&mut self, notable_trait: &mut NotableTrait) {
+        visit_notable_trait_mut(self, notable_trait)
+    }
+
+    fn visit_scraped_example_mut(&mut self, example: &mut ScrapedExample) {
+        visit_scraped_example_mut(self, example)
+    }
+
+    fn visit_layout_mut(&mut self, layout: &mut TypeLayout) {
+        visit_layout_mut(self, layout)
+    }
+
+    fn visit_description_mut(&mut self, description: &mut Description<'a>) {
+        visit_description_mut(self, description)
+    }
+
+    fn visit_description_section_mut(&mut self, section: &mut DescriptionSection<'a>) {
+        visit_description_section_mut(self, section)
+    }
+
+    fn visit_fragment_mut(&mut self, fragment: &mut Fragment<'a>) {
+        visit_fragment_mut(self, fragment)
+    }
+
+    /// The text of a `Fragment::Raw`, the very leaves of the Page.
+    fn visit_text_mut(&mut self, text: &mut Cow<'a, str>) {}
+}
+
+pub fn visit_page_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, page: &mut Page<'a>) {
+    visitor.visit_sidebar_mut(&mut page.sidebar);
+    visit_fragments_mut(visitor, &mut page.entry);
+    visitor.visit_description_mut(&mut page.introduction);
+    visitor.visit_main_content_mut(&mut page.main_content);
+    for example in &mut page.examples {
+        visitor.visit_scraped_example_mut(example);
+    }
+}
+
+pub fn visit_sidebar_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, sidebar: &mut Sidebar) {
+    for section in &mut sidebar.sections {
+        visitor.visit_sidebar_section_mut(section);
+    }
+    if let Some(module) = &mut sidebar.module {
+        visitor.visit_module_navigation_mut(module);
+    }
+}
+
+pub fn visit_sidebar_section_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, section: &mut SidebarSection) {
+    for item in &mut section.items {
+        visitor.visit_sidebar_item_mut(item);
+    }
+}
+
+pub fn visit_module_navigation_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, module: &mut ModuleNavigation) {
+    for section in &mut module.sections {
+        visitor.visit_sidebar_section_mut(section);
+    }
+}
+
+pub fn visit_main_content_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, main_content: &mut MainContent<'a>) {
+    for section in &mut main_content.0 {
+        visitor.visit_section_mut(section);
+    }
+}
+
+pub fn visit_section_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, section: &mut Section<'a>) {
+    visitor.visit_fragment_mut(&mut section.name);
+    visitor.visit_section_content_mut(&mut section.content);
+}
+
+pub fn visit_section_content_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, content: &mut SectionContent<'a>) {
+    match content {
+        SectionContent::Dummy => (),
+        SectionContent::Layout(layout) => visitor.visit_layout_mut(layout),
+        SectionContent::Methods(methods) => methods.iter_mut().for_each(|method| visitor.visit_method_mut(method)),
+        SectionContent::Fields(fields) => fields.iter_mut().for_each(|field| visitor.visit_field_mut(field)),
+        SectionContent::Implementations(implementations) => implementations
+            .iter_mut()
+            .for_each(|implementation| visitor.visit_implementation_mut(implementation)),
+        SectionContent::ObjectSafety(fragments) => visit_fragments_mut(visitor, fragments),
+        SectionContent::RequiredAssociatedTypes(types) => types
+            .iter_mut()
+            .for_each(|r#type| visitor.visit_required_associated_type_mut(r#type)),
+        SectionContent::TraitImplementations(implementations) => implementations
+            .iter_mut()
+            .for_each(|implementation| visitor.visit_trait_implementation_mut(implementation)),
+        SectionContent::Variants(variants) => variants.iter_mut().for_each(|variant| visitor.visit_variant_mut(variant)),
+    }
+}
+
+pub fn visit_field_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, field: &mut Field<'a>) {
+    visit_fragments_mut(visitor, &mut field.r#type);
+    if let Some(deprecation) = &mut field.deprecation {
+        visit_fragments_mut(visitor, deprecation);
+    }
+    visitor.visit_description_mut(&mut field.description);
+}
+
+pub fn visit_implementation_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, implementation: &mut Implementation<'a>) {
+    visitor.visit_fragment_mut(&mut implementation.inherent_impl);
+    for method in &mut implementation.methods {
+        visitor.visit_method_mut(method);
+    }
+}
+
+pub fn visit_required_associated_type_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, r#type: &mut RequiredAssociatedType<'a>) {
+    visitor.visit_fragment_mut(&mut r#type.name);
+    visitor.visit_fragment_mut(&mut r#type.description);
+}
+
+pub fn visit_trait_implementation_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, implementation: &mut TraitImplementation<'a>) {
+    visitor.visit_fragment_mut(&mut implementation.trait_impl);
+    for method in &mut implementation.methods {
+        visitor.visit_method_mut(method);
+    }
+}
+
+pub fn visit_variant_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, variant: &mut Variant<'a>) {
+    visitor.visit_fragment_mut(&mut variant.name);
+    for field in &mut variant.fields {
+        visitor.visit_field_mut(field);
+    }
+    visitor.visit_description_mut(&mut variant.description);
+}
+
+pub fn visit_method_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, method: &mut Method<'a>) {
+    visit_fragments_mut(visitor, &mut method.signature);
+    for notable_trait in &mut method.notable_traits {
+        visitor.visit_notable_trait_mut(notable_trait);
+    }
+    visitor.visit_description_mut(&mut method.description);
+    for example in &mut method.examples {
+        visitor.visit_scraped_example_mut(example);
+    }
+}
+
+/// Notable traits are mere paths, with nothing below them to walk.
+pub fn visit_notable_trait_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, notable_trait: &mut NotableTrait) {}
+
+/// Scraped examples keep their code as plain text rather than Fragments.
+pub fn visit_scraped_example_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, example: &mut ScrapedExample) {}
+
+/// Layouts are sizes alone, so the walk ends with them.
+pub fn visit_layout_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, layout: &mut TypeLayout) {}
+
+pub fn visit_description_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, description: &mut Description<'a>) {
+    visit_fragments_mut(visitor, &mut description.introduction);
+    for section in &mut description.sections {
+        visitor.visit_description_section_mut(section);
+    }
+}
+
+pub fn visit_description_section_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, section: &mut DescriptionSection<'a>) {
+    visitor.visit_fragment_mut(&mut section.name);
+    visit_fragments_mut(visitor, &mut section.content);
+}
+
+pub fn visit_fragment_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, fragment: &mut Fragment<'a>) {
+    match fragment {
+        Fragment::Bold(fragments) => visit_fragments_mut(visitor, fragments),
+        Fragment::Code(fragment)
+        | Fragment::CodeBlock(fragment)
+        | Fragment::Colored(fragment, _)
+        | Fragment::Link { content: fragment, .. } => visitor.visit_fragment_mut(fragment),
+        Fragment::Raw(text) => visitor.visit_text_mut(text),
+    }
+}
+
+fn visit_fragments_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, fragments: &mut [Fragment<'a>]) {
+    for fragment in fragments {
+        visitor.visit_fragment_mut(fragment);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{
+        visit_fragment_mut,
+        VisitMut
+    };
+    use crate::{
+        helper_types::fragment::Fragment,
+        main_content::test_pages::rustdoc_page,
+    };
+
+    /// Unwraps bold text, as for terminals which can't show it.
+    struct Unbold;
+
+    impl<'a> VisitMut<'a> for Unbold {
+        fn visit_fragment_mut(&mut self, fragment: &mut Fragment<'a>) {
+            visit_fragment_mut(self, fragment);
+            if let Fragment::Bold(fragments) = fragment {
+                let text = fragments.iter().map(Fragment::to_string).collect::<String>();
+                *fragment = Fragment::Raw(Cow::Owned(text));
+            }
+        }
+    }
+
+    #[test]
+    fn visit_page_mut() {
+        let mut page = rustdoc_page("struct", "\
+            <details class=\"toggle top-doc\" open><summary></summary><div class=\"docblock\"><p>A <strong>bold <code>S</code></strong>.</p></div></details>");
+        Unbold.visit_page_mut(&mut page);
+        assert_eq!(page.introduction.to_string(), "A bold S.");
+        assert!(!page.introduction.introduction.iter().any(|fragment| matches!(fragment, Fragment::Bold(_))));
+    }
+}